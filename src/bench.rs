@@ -3,6 +3,16 @@ mod rtf;
 mod translation_model;
 mod translation_parse;
 mod translation;
+mod error;
+mod key_combo;
+mod key_names;
+mod dialect;
+mod trie;
+mod search;
+mod history;
+mod order;
+mod stroke;
+mod system;
 
 #[macro_use]
 extern crate lazy_static;
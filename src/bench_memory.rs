@@ -0,0 +1,75 @@
+mod dict;
+mod rtf;
+mod translation_model;
+mod translation_parse;
+mod translation;
+mod error;
+mod key_combo;
+mod key_names;
+mod dialect;
+mod trie;
+mod search;
+mod history;
+mod order;
+mod stroke;
+mod system;
+
+#[macro_use]
+extern crate lazy_static;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dict::Dictionary;
+
+// Tracks the bytes currently allocated, so the memory used by a dictionary can
+// be measured as the difference before and after building it.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    System.dealloc(ptr, layout)
+  }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// A dictionary shaped like a large real one: mostly one- and two-stroke
+// outlines, with many translations defined by more than one outline.
+fn generated_dict(size: usize) -> Dictionary {
+  let left = ["S", "T", "K", "P", "W", "H", "R", "TK", "PW", "HR", "ST", "KP"];
+  let vowels = ["A", "O", "E", "U", "AO", "AE", "AU", "OE", "EU", "OU"];
+  let right = ["F", "R", "P", "B", "L", "G", "T", "S", "D", "Z", "PB", "LG"];
+  let mut d = Dictionary::new("Plover");
+  for i in 0..size {
+    let stroke = |n: usize| format!("{}{}{}",
+      left[n % left.len()], vowels[n / left.len() % vowels.len()],
+      right[n / left.len() / vowels.len() % right.len()]);
+    let steno = match i / 1440 {
+      0 => stroke(i),
+      n => format!("{}/{}", stroke(i), stroke(n)),
+    };
+    d.add_entry(steno, format!("word{}", i / 3), None);
+  }
+  d
+}
+
+// Kept apart from the timing benchmarks, since counting every allocation
+// slows them all down.
+fn main() {
+  for size in [1000, 200000].iter() {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let dict = generated_dict(*size);
+    let used = ALLOCATED.load(Ordering::Relaxed) - before;
+    println!("memory/{}: {} bytes ({} per entry)", size, used, used / dict.len());
+  }
+}
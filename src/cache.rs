@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::dict::{Dictionary, Entry};
+use crate::error::Error;
+use crate::rtf::{ParseOptions, ParsedDictionary, parse_file_with_options};
+use crate::system::NumberKey;
+
+static MAGIC: &[u8; 8] = b"RTFCRE\0C";
+/// The version of the cache format, bumped whenever the layout or the way
+/// outlines are read changes so caches written by older versions are rebuilt
+/// rather than misread.
+pub const CACHE_VERSION: u32 = 2;
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// The 64-bit FNV-1a hash of `data`, used to tie a cache to its source and
+/// to detect corrupted caches.
+pub fn checksum(data: &[u8]) -> u64 {
+  data.iter().fold(FNV_OFFSET, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+fn write_u32(buf: &mut Vec<u8>, value: usize) {
+  buf.extend_from_slice(&(value as u32).to_le_bytes());
+}
+
+fn write_str(buf: &mut Vec<u8>, value: &str) {
+  write_u32(buf, value.len());
+  buf.extend_from_slice(value.as_bytes());
+}
+
+/// Write `dict` to `writer` in the binary cache format, along with its
+/// reverse lookup indexes and longest key. `source` is the RTF the dictionary
+/// was read from; the cache is only valid for that exact input.
+pub fn write_cache(dict: &Dictionary, source: &str, writer: &mut dyn io::Write)
+    -> Result<(), io::Error> {
+  let mut body = vec![];
+  write_str(&mut body, &dict.cre_system);
+  write_u32(&mut body, dict.longest_key);
+
+  // The indexes refer to entries by their position, which is the order they
+  // are written in
+  let mut ids: HashMap<&str, usize> = HashMap::new();
+  write_u32(&mut body, dict.entries.len());
+  for (id, entry) in dict.entries.values().enumerate() {
+    ids.insert(&entry.steno, id);
+    write_str(&mut body, &entry.steno);
+    write_str(&mut body, &entry.translation);
+    match entry.comment() {
+      Some(comment) => {
+        body.push(1);
+        write_str(&mut body, &comment);
+      },
+      None => body.push(0),
+    }
+  }
+
+  // Each translation is written as the entries that define it
+  write_u32(&mut body, dict.reverse_entries.len());
+  for outlines in dict.reverse_entries.values() {
+    write_u32(&mut body, outlines.len());
+    for steno in outlines {
+      write_u32(&mut body, ids[&**steno]);
+    }
+  }
+
+  // Each normalized translation is written with one entry for each
+  // translation that reduces to it
+  write_u32(&mut body, dict.normalized_reverse_entries.len());
+  for (normalized, translations) in &dict.normalized_reverse_entries {
+    write_str(&mut body, normalized);
+    write_u32(&mut body, translations.len());
+    for translation in translations {
+      write_u32(&mut body, ids[&*dict.reverse_entries[translation][0]]);
+    }
+  }
+
+  writer.write_all(MAGIC)?;
+  writer.write_all(&CACHE_VERSION.to_le_bytes())?;
+  writer.write_all(&checksum(source.as_bytes()).to_le_bytes())?;
+  writer.write_all(&checksum(&body).to_le_bytes())?;
+  writer.write_all(&body)
+}
+
+fn invalid(reason: &str) -> Error {
+  Error::InvalidCache { reason: reason.to_string() }
+}
+
+struct Reader<'a> {
+  data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+  fn bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+    if len > self.data.len() {
+      return Err(invalid("unexpected end of cache"));
+    }
+    let (bytes, rest) = self.data.split_at(len);
+    self.data = rest;
+    Ok(bytes)
+  }
+
+  fn u8(&mut self) -> Result<u8, Error> {
+    Ok(self.bytes(1)?[0])
+  }
+
+  fn u32(&mut self) -> Result<usize, Error> {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(self.bytes(4)?);
+    Ok(u32::from_le_bytes(bytes) as usize)
+  }
+
+  fn u64(&mut self) -> Result<u64, Error> {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(self.bytes(8)?);
+    Ok(u64::from_le_bytes(bytes))
+  }
+
+  fn string(&mut self) -> Result<String, Error> {
+    let len = self.u32()?;
+    String::from_utf8(self.bytes(len)?.to_vec())
+      .map_err(|_| invalid("string is not valid UTF-8"))
+  }
+
+  // A list of entry IDs, checked against the number of entries
+  fn ids(&mut self, entries: usize) -> Result<Vec<usize>, Error> {
+    (0..self.u32()?)
+      .map(|_| match self.u32()? {
+        id if id < entries => Ok(id),
+        _ => Err(invalid("entry ID out of range")),
+      })
+      .collect()
+  }
+}
+
+/// Read a dictionary from `data`, a cache written by `write_cache`. Fails
+/// with `Error::StaleCache` if the cache was written by a different version
+/// or for a source other than `source`.
+pub fn read_cache(data: &[u8], source: &str) -> Result<Dictionary, Error> {
+  let mut reader = Reader { data };
+  if reader.bytes(MAGIC.len())? != MAGIC {
+    return Err(invalid("not a dictionary cache"));
+  }
+  if reader.u32()? as u32 != CACHE_VERSION || reader.u64()? != checksum(source.as_bytes()) {
+    return Err(Error::StaleCache);
+  }
+  if reader.u64()? != checksum(reader.data) {
+    return Err(invalid("checksum mismatch"));
+  }
+
+  let mut dict = Dictionary::new(&reader.string()?);
+  let longest_key = reader.u32()?;
+
+  let mut entries = vec![];
+  for _ in 0..reader.u32()? {
+    let steno = reader.string()?;
+    let translation = reader.string()?;
+    let comment = match reader.u8()? {
+      0 => None,
+      _ => Some(reader.string()?),
+    };
+    entries.push(Entry::new(&steno, &translation, comment.as_deref()));
+  }
+
+  for _ in 0..reader.u32()? {
+    let ids = reader.ids(entries.len())?;
+    let translation = match ids.first() {
+      Some(id) => entries[*id].translation.clone(),
+      None => return Err(invalid("translation has no entries")),
+    };
+    // Entries with the same translation share a single copy of it
+    let mut outlines = vec![];
+    for id in ids {
+      if entries[id].translation != translation {
+        return Err(invalid("reverse index does not match entries"));
+      }
+      entries[id].translation = translation.clone();
+      outlines.push(entries[id].steno.clone());
+    }
+    dict.reverse_entries.insert(translation, outlines);
+  }
+
+  for _ in 0..reader.u32()? {
+    let normalized = reader.string()?;
+    let translations: Vec<Arc<str>> = reader.ids(entries.len())?.into_iter()
+      .map(|id| entries[id].translation.clone())
+      .collect();
+    let key = match translations.iter().find(|tl| ***tl == normalized) {
+      Some(translation) => translation.clone(),
+      None => Arc::from(normalized),
+    };
+    dict.normalized_reverse_entries.insert(key, translations);
+  }
+
+  if !reader.data.is_empty() {
+    return Err(invalid("unexpected data after end of cache"));
+  }
+  for entry in entries {
+    dict.count_strokes(&entry.steno, true);
+    dict.entries.insert(entry.steno.clone(), entry);
+  }
+  if dict.longest_key != longest_key {
+    return Err(invalid("longest key does not match entries"));
+  }
+  Ok(dict)
+}
+
+/// Parse the RTF dictionary `input` like `parse_rtf`, reusing the cache at
+/// `cache` if it was written for the same input. Otherwise the cache is
+/// rebuilt; failing to write it is not an error, since it only speeds up
+/// later loads.
+pub fn parse_rtf_cached(input: &str, cache: &Path) -> Option<Dictionary> {
+  parse_rtf_cached_with_options(input, cache, &ParseOptions::default())
+}
+
+/// Parse the RTF dictionary `input` like `parse_rtf_with_options`, reusing
+/// the cache at `cache` if it was written for the same input and number key
+/// handling.
+pub fn parse_rtf_cached_with_options(input: &str, cache: &Path, options: &ParseOptions)
+    -> Option<Dictionary> {
+  // Outlines are stored after their number keys are rewritten, so a cache
+  // written with one policy is stale for any other
+  let source = match options.number_key {
+    NumberKey::Keep => input.to_string(),
+    policy => format!("{}\0{:?}", input, policy),
+  };
+
+  if let Ok(data) = fs::read(cache) {
+    if let Ok(mut dict) = read_cache(&data, &source) {
+      options.apply(&mut dict);
+      return Some(dict);
+    }
+  }
+
+  let dict = match parse_file_with_options(input.trim(), options, None) {
+    Ok((_, ParsedDictionary { dict, .. })) => dict,
+    Err(_) => return None,
+  };
+  let mut data = vec![];
+  if write_cache(&dict, &source, &mut data).is_ok() {
+    let _ = fs::write(cache, data);
+  }
+  Some(dict)
+}
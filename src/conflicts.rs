@@ -0,0 +1,75 @@
+use crate::dict::{Dictionary, Entry};
+use crate::translation::format_plover_output;
+
+/// A multi-stroke entry whose strokes can also be read as a sequence of
+/// shorter outlines, each defined on its own. Writing those outlines one
+/// after another translates as the multi-stroke entry instead.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BoundaryConflict {
+  pub entry: Entry,
+  /// Each way of splitting the outline into defined entries, in order.
+  pub readings: Vec<Vec<Entry>>,
+}
+
+impl BoundaryConflict {
+  /// The text each reading writes, formatted as Plover writes its
+  /// translations one after another, so `car pet {^s}` writes `car pets`.
+  pub fn reading_translations(&self) -> Vec<String> {
+    self.readings.iter()
+      .map(|reading| format_plover_output(&reading.iter()
+        .map(|entry| &*entry.translation)
+        .collect::<Vec<_>>()))
+      .collect()
+  }
+
+  /// Each reading along with the text it writes, as in
+  /// `reading_translations`.
+  pub fn readings_with_text(&self) -> Vec<(String, &[Entry])> {
+    self.reading_translations().into_iter()
+      .zip(self.readings.iter().map(Vec::as_slice))
+      .collect()
+  }
+}
+
+// Every way to split `strokes` into outlines of the dictionary, appending
+// each to `readings`. Only outlines up to the longest key are looked up.
+fn split(dict: &Dictionary, strokes: &[&str], prefix: &mut Vec<Entry>,
+    readings: &mut Vec<Vec<Entry>>) {
+  if strokes.is_empty() {
+    readings.push(prefix.clone());
+    return;
+  }
+  for len in 1..=strokes.len().min(dict.longest_key) {
+    if let Some(entry) = dict.entries.get(&*strokes[..len].join("/")) {
+      prefix.push(entry.clone());
+      split(dict, &strokes[len..], prefix, readings);
+      prefix.pop();
+    }
+  }
+}
+
+/// The readings of `entry` as two or more shorter outlines of `dict`, with
+/// the shortest first outlines first.
+pub fn readings(dict: &Dictionary, entry: &Entry) -> Vec<Vec<Entry>> {
+  let strokes: Vec<&str> = entry.steno.split('/').collect();
+  let mut readings = vec![];
+  for len in 1..strokes.len() {
+    if let Some(first) = dict.entries.get(&*strokes[..len].join("/")) {
+      let mut prefix = vec![first.clone()];
+      split(dict, &strokes[len..], &mut prefix, &mut readings);
+    }
+  }
+  readings
+}
+
+/// Find every multi-stroke entry of `dict` that can also be read as shorter
+/// outlines of it, in file order.
+pub fn boundary_conflicts(dict: &Dictionary) -> Vec<BoundaryConflict> {
+  dict.entries.values()
+    .filter(|entry| entry.steno.contains('/'))
+    .filter_map(|entry| match readings(dict, entry) {
+      readings if readings.is_empty() => None,
+      readings => Some(BoundaryConflict { entry: entry.clone(), readings }),
+    })
+    .collect()
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::key_combo::KeyCombo;
+use crate::translation::{object_to_rtf, parse_translation};
+use crate::translation_model::Object;
+use crate::translation_parse::{objects_to_plover, parse_translation as parse_rtf_translation};
+
+/// The flavour of RTF/CRE to write. `Plover` writes the Plover-specific
+/// `\cxplvr*` extensions; the others only use standard RTF/CRE constructs,
+/// since no vendor-specific ones have been verified against each CAT
+/// system's documentation yet.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Dialect {
+  #[default]
+  Plover,
+  CaseCatalyst,
+  Eclipse,
+  ProCat,
+  StenoCat,
+}
+
+#[derive(Debug)]
+pub struct DialectParseError {}
+impl fmt::Display for DialectParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "unknown dialect")
+  }
+}
+
+impl FromStr for Dialect {
+  type Err = DialectParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "plover" => Ok(Self::Plover),
+      "catalyst" | "case-catalyst" => Ok(Self::CaseCatalyst),
+      "eclipse" => Ok(Self::Eclipse),
+      "procat" => Ok(Self::ProCat),
+      "stenocat" => Ok(Self::StenoCat),
+      _ => Err(DialectParseError {}),
+    }
+  }
+}
+
+/// A Plover construct that could not be written as-is in a given dialect.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct DialectWarning {
+  pub object: String,
+  pub message: &'static str,
+  /// Whether the construct was left out entirely, rather than approximated.
+  pub dropped: bool,
+}
+
+fn text(string: &str) -> String {
+  object_to_rtf(&Object::RawString(string.to_string()))
+}
+
+enum Mapping {
+  Native(String),
+  Approximate(String, &'static str),
+  Dropped(&'static str),
+}
+
+fn map_object(obj: &Object) -> Mapping {
+  match obj {
+    Object::RawString(_) | Object::Space | Object::HardSpace |
+    Object::Paragraph(_) | Object::Punctuation(_) | Object::Fingerspell(_) |
+    Object::DeleteStroke | Object::AttachRaw |
+    Object::ForceCapitalize | Object::ForceLowercase =>
+      Mapping::Native(object_to_rtf(obj)),
+    Object::Stitch(letters) =>
+      Mapping::Approximate(format!("{{\\cxfing {}}}", letters),
+        "stitching is written as fingerspelling"),
+    Object::AttachPrefix(string) => Mapping::Native(format!("{}\\cxds ", text(string))),
+    Object::AttachSuffix(string) => Mapping::Native(format!("\\cxds {}", text(string))),
+    Object::AttachInfix(string) => Mapping::Native(format!("\\cxds {}\\cxds ", text(string))),
+    Object::CarryCapRaw(string) =>
+      Mapping::Approximate(text(string),
+        "carrying capitalization is not supported"),
+    Object::CarryCapPrefix(string) =>
+      Mapping::Approximate(format!("{}\\cxds ", text(string)),
+        "carrying capitalization is not supported"),
+    Object::CarryCapSuffix(string) =>
+      Mapping::Approximate(format!("\\cxds {}", text(string)),
+        "carrying capitalization is not supported"),
+    Object::CarryCapInfix(string) =>
+      Mapping::Approximate(format!("\\cxds {}\\cxds ", text(string)),
+        "carrying capitalization is not supported"),
+    Object::ForceCapitalizeWord =>
+      Mapping::Approximate("\\cxfc ".to_string(),
+        "only the first letter of the next word is capitalized"),
+    Object::Noop => Mapping::Dropped("no-ops have no equivalent"),
+    Object::Cancel => Mapping::Dropped("cancelling formatting has no equivalent"),
+    Object::WordEnd => Mapping::Dropped("word end markers have no equivalent"),
+    Object::Command(_, _) => Mapping::Dropped("Plover commands have no equivalent"),
+    Object::Meta(_, _) => Mapping::Dropped("Plover metas have no equivalent"),
+    Object::Macro(_, _) => Mapping::Dropped("Plover macros have no equivalent"),
+    Object::KeyCombo(_) => Mapping::Dropped("key combinations have no equivalent"),
+    Object::Currency(_, _) => Mapping::Dropped("currency formatting has no equivalent"),
+    Object::CaseMode(_) | Object::SpaceMode(_) | Object::ResetCaseAndSpace =>
+      Mapping::Dropped("case and space modes have no equivalent"),
+    Object::RepeatLastStroke | Object::RetroToggleStar |
+    Object::RetroInsertSpace | Object::RetroDeleteSpace |
+    Object::RetroForceCapitalize | Object::RetroForceLowercase |
+    Object::RetroForceCapitalizeWord =>
+      Mapping::Dropped("retroactive operations have no equivalent"),
+    Object::Unknown(_) => Mapping::Dropped("unrecognized Plover syntax has no equivalent"),
+    Object::OrthoAttach | Object::RtfControl(_, _) => Mapping::Native("".to_string()),
+  }
+}
+
+/// Convert a Plover translation into the given dialect of RTF, returning the
+/// RTF along with a warning for every construct that had to be approximated
+/// or dropped, and for every invalid key combination.
+pub fn format_plover_to_rtf_dialect(tl: &str, dialect: Dialect) -> (String, Vec<DialectWarning>) {
+  let mut warnings = vec![];
+  let rtf = parse_translation(tl).iter()
+    .map(|obj| {
+      if dialect == Dialect::Plover {
+        if let Object::KeyCombo(keys) = obj {
+          if KeyCombo::parse(keys).is_err() {
+            warnings.push(DialectWarning {
+              object: format!("{:?}", obj),
+              message: "not a valid key combination, written as is",
+              dropped: false,
+            });
+          }
+        }
+        return object_to_rtf(obj);
+      }
+      match map_object(obj) {
+        Mapping::Native(rtf) => rtf,
+        Mapping::Approximate(rtf, message) => {
+          warnings.push(DialectWarning { object: format!("{:?}", obj), message, dropped: false });
+          rtf
+        },
+        Mapping::Dropped(message) => {
+          warnings.push(DialectWarning { object: format!("{:?}", obj), message, dropped: true });
+          "".to_string()
+        },
+      }
+    })
+    .collect::<Vec<String>>()
+    .join("");
+  (rtf, warnings)
+}
+
+/// A mapping from the RTF control words used by a particular CAT system to
+/// Plover objects, for reading dictionaries exported from that system.
+///
+/// Controls are looked up by name including any numeric parameter (`b0`)
+/// first, then by name alone (`b`). A control mapped to no objects is known
+/// but deliberately ignored, such as entry metadata.
+#[derive(Clone, Debug, Default)]
+pub struct ImportProfile {
+  controls: HashMap<String, Vec<Object>>,
+}
+
+impl ImportProfile {
+  /// An empty profile, which maps nothing beyond what the parser already
+  /// understands.
+  pub fn new() -> Self {
+    Self { controls: HashMap::new() }
+  }
+
+  /// The built-in profile for the given dialect.
+  pub fn for_dialect(dialect: Dialect) -> Self {
+    let mut profile = Self::new();
+
+    // Plain RTF symbols that show up in exports from most CAT software
+    profile.map("emdash", "\\u8212");
+    profile.map("endash", "\\u8211");
+    profile.map("lquote", "\\u8216");
+    profile.map("rquote", "\\u8217");
+    profile.map("ldblquote", "\\u8220");
+    profile.map("rdblquote", "\\u8221");
+    profile.map("bullet", "\\u8226");
+    profile.map("par", "\\par\\s0");
+    profile.ignore("plain");
+
+    // Entry metadata written by Case CATalyst and Eclipse. Other
+    // vendor-specific controls can be added to the profile with `map`.
+    match dialect {
+      Dialect::CaseCatalyst | Dialect::Eclipse => {
+        profile.ignore("cxsvatdictflags");
+        profile.ignore("cxsvatdictentrydate");
+      },
+      Dialect::Plover | Dialect::ProCat | Dialect::StenoCat => {},
+    }
+
+    profile
+  }
+
+  /// Map `control` to the objects in `rtf`, a snippet of RTF that the parser
+  /// already understands (e.g. `\cxfc` or `\u8212`).
+  pub fn map(&mut self, control: &str, rtf: &str) {
+    self.map_objects(control, parse_rtf_translation(rtf));
+  }
+
+  pub fn map_objects(&mut self, control: &str, objects: Vec<Object>) {
+    self.controls.insert(control.to_string(), objects);
+  }
+
+  /// Accept `control` without mapping it to anything.
+  pub fn ignore(&mut self, control: &str) {
+    self.map_objects(control, vec![]);
+  }
+
+  fn lookup(&self, control: &str) -> Option<&Vec<Object>> {
+    match self.controls.get(control) {
+      Some(objects) => Some(objects),
+      None => self.controls.get(control.trim_end_matches(|c: char| c == '-' || c.is_ascii_digit())),
+    }
+  }
+}
+
+/// Convert RTF into Plover syntax using the given import profile, returning
+/// the translation along with the names of any controls that could not be
+/// mapped.
+pub fn format_rtf_to_plover_with_profile(tl: &str, profile: &ImportProfile) -> (String, Vec<String>) {
+  let mut unmapped = vec![];
+  let objects = parse_rtf_translation(tl).into_iter()
+    .flat_map(|obj| match obj {
+      Object::RtfControl(name, _) => match profile.lookup(&name) {
+        Some(objects) => objects.clone(),
+        None => {
+          unmapped.push(name);
+          vec![]
+        },
+      },
+      obj => vec![obj],
+    })
+    .collect::<Vec<Object>>();
+  (objects_to_plover(&objects), unmapped)
+}
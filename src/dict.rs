@@ -1,22 +1,38 @@
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::vec::Vec;
 use std::result::Result;
 use std::io;
+use std::sync::Arc;
 
-use crate::translation::format_plover_to_rtf;
+use crate::dialect::{Dialect, DialectWarning, format_plover_to_rtf_dialect};
+use crate::error::Error;
+use crate::history::{Changeset, Edit, History, Positions};
+use crate::key_combo::validate_key_combos;
+use crate::order::{Order, WriteOptions, sort_entries};
+use crate::search::{Pattern, SearchField};
+use crate::system::StenoSystem;
+use crate::translation::{format_plover_to_rtf, normalize_translation};
+use crate::trie::StrokeTrie;
 
 use linked_hash_map::LinkedHashMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EntryMetadata {
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
   comment: Option<String>,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Entry {
-  pub steno: String,
-  pub translation: String,
+  pub(crate) steno: Arc<str>,
+  pub(crate) translation: Arc<str>,
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
   metadata: Option<EntryMetadata>,
 }
 
@@ -24,15 +40,20 @@ impl Entry {
   #[allow(dead_code)]
   pub fn new(steno: &str, translation: &str, comment: Option<&str>) -> Self {
     Self {
-      steno: String::from(steno),
-      translation: String::from(translation),
-      metadata: match comment {
-        Some(comm) => Some(EntryMetadata { comment: Some(String::from(comm)) }),
-        None => None,
-      },
+      steno: Arc::from(steno),
+      translation: Arc::from(translation),
+      metadata: comment.map(|comment| EntryMetadata { comment: Some(String::from(comment)) }),
     }
   }
 
+  pub fn steno(&self) -> &str {
+    &self.steno
+  }
+
+  pub fn translation(&self) -> &str {
+    &self.translation
+  }
+
   pub fn comment(&self) -> Option<String> {
     Some(self.metadata.as_ref()?.comment.as_ref()?.clone())
   }
@@ -46,11 +67,24 @@ impl Entry {
   }
 
   pub fn write(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+    self.write_rtf(writer, &format_plover_to_rtf(&self.translation))
+  }
+
+  /// Write the entry in the given dialect of RTF, returning the constructs
+  /// in the translation that could not be written as-is.
+  pub fn write_dialect(&self, writer: &mut dyn io::Write, dialect: Dialect)
+      -> Result<Vec<DialectWarning>, io::Error> {
+    let (rtf, warnings) = format_plover_to_rtf_dialect(&self.translation, dialect);
+    self.write_rtf(writer, &rtf)?;
+    Ok(warnings)
+  }
+
+  fn write_rtf(&self, writer: &mut dyn io::Write, rtf: &str) -> Result<(), io::Error> {
     write!(writer, "{{\\*\\cxs {}}}{}{}\n",
       self.steno.chars().map(|c|
         if (c as u32) > 255 { format!("\\u{} ", c as u32) }
         else { String::from(c) }).collect::<Vec<String>>().join(""),
-      format_plover_to_rtf(&self.translation),
+      rtf,
       match &self.metadata {
         Some(EntryMetadata { comment: Some(comment) }) =>
           format!("{{\\*\\cxcomment {}}}", comment),
@@ -60,11 +94,41 @@ impl Entry {
   }
 }
 
+/// Two entries whose outlines became the same outline when normalized. As
+/// with an outline defined twice in a file, the later entry replaces the
+/// earlier one.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MergedOutline {
+  /// The normalized outline.
+  pub steno: String,
+  /// The earlier entry, with its outline as it was before normalizing.
+  pub replaced: Entry,
+  /// The later entry, with its outline as it was before normalizing.
+  pub kept: Entry,
+}
+
+/// A steno dictionary. Each outline and translation is allocated once and
+/// shared by the entries and the reverse lookup indexes, and entries with the
+/// same translation share a single copy of it.
+#[derive(Clone)]
 pub struct Dictionary {
   pub cre_system: String,
-  pub entries: LinkedHashMap<String, Entry>,
-  pub reverse_entries: HashMap<String, HashSet<String>>,
+  pub(crate) entries: LinkedHashMap<Arc<str>, Entry>,
+  pub(crate) reverse_entries: HashMap<Arc<str>, Vec<Arc<str>>>,
+  /// Maps each normalized translation to the translations that reduce to it.
+  pub(crate) normalized_reverse_entries: HashMap<Arc<str>, Vec<Arc<str>>>,
+  /// The number of strokes in the longest outline.
   pub longest_key: usize,
+  /// The steno system outlines are parsed, validated and sorted with.
+  /// English stenotype unless set otherwise.
+  pub steno_system: Arc<StenoSystem>,
+  normalize: bool,
+  stroke_counts: BTreeMap<usize, usize>,
+  trie: Option<StrokeTrie>,
+  history: History,
+  /// Where each outline is in the file, built the first time it's needed in
+  /// a transaction.
+  positions: Option<Positions>,
 }
 
 static FILE_HEADER: &str = "{\\rtf1\\ansi{\\*\\cxrev100}\\cxdict";
@@ -77,69 +141,561 @@ impl Dictionary {
       cre_system: String::from(cre_system),
       entries: LinkedHashMap::new(),
       reverse_entries: HashMap::new(),
+      normalized_reverse_entries: HashMap::new(),
       longest_key: 0,
+      steno_system: StenoSystem::builtin("english").unwrap(),
+      normalize: false,
+      stroke_counts: BTreeMap::new(),
+      trie: None,
+      history: History::default(),
+      positions: None,
     }
   }
 
-  pub fn add_entry(&mut self, steno: String, translation: String, comment: Option<String>) {
-    if let Some(entry) = self.entries.get(&steno) {
-      // This outline is already defined and being overridden below, remove the
-      // corresponding reverse entry so we don't inadvertently return that on
-      // reverse lookups
-      if let Some(rev_entry) = self.reverse_entries.get_mut(&entry.translation) {
-        rev_entry.remove(&steno);
+  /// Build a stroke-level index of the outlines, which speeds up prefix and
+  /// continuation queries and is kept up to date as entries change.
+  pub fn enable_trie(&mut self) {
+    let mut trie = StrokeTrie::new();
+    for steno in self.entries.keys() {
+      trie.insert(steno);
+    }
+    self.trie = Some(trie);
+  }
+
+  pub fn disable_trie(&mut self) {
+    self.trie = None;
+  }
+
+  pub fn trie(&self) -> Option<&StrokeTrie> {
+    self.trie.as_ref()
+  }
+
+  // Add or remove an outline from the histogram of stroke counts, keeping the
+  // longest key up to date
+  pub(crate) fn count_strokes(&mut self, steno: &str, added: bool) {
+    let strokes = steno.chars().filter(|c| *c == '/').count() + 1;
+    if added {
+      *self.stroke_counts.entry(strokes).or_default() += 1;
+    } else if let Some(count) = self.stroke_counts.get_mut(&strokes) {
+      *count -= 1;
+      if *count == 0 {
+        self.stroke_counts.remove(&strokes);
       }
     }
+    self.longest_key = self.stroke_counts.keys().next_back().copied().unwrap_or(0);
+  }
 
-    self.entries.insert(
-      steno.clone(),
-      Entry {
-        steno: steno.clone(),
-        translation: translation.clone(),
-        metadata: match comment {
-          Some(comm) => Some(EntryMetadata { comment: Some(comm) }),
-          None => None,
-        },
+  /// The number of outlines with each number of strokes.
+  pub fn stroke_counts(&self) -> &BTreeMap<usize, usize> {
+    &self.stroke_counts
+  }
+
+  fn remove_reverse_entry(&mut self, steno: &str, translation: &str) {
+    if let Some(rev_entry) = self.reverse_entries.get_mut(translation) {
+      rev_entry.retain(|outline| &**outline != steno);
+
+      if rev_entry.is_empty() {
+        self.reverse_entries.remove(translation);
+
+        let normalized = normalize_translation(translation);
+        if let Some(translations) = self.normalized_reverse_entries.get_mut(normalized.as_str()) {
+          translations.retain(|tl| &**tl != translation);
+          if translations.is_empty() {
+            self.normalized_reverse_entries.remove(normalized.as_str());
+          }
+        }
+      }
+    }
+  }
+
+  fn add_reverse_entry(&mut self, steno: &Arc<str>, translation: &Arc<str>) {
+    match self.reverse_entries.get_mut(translation) {
+      Some(outlines) => {
+        if !outlines.contains(steno) {
+          outlines.push(steno.clone());
+        }
+        // Only the first outline for a translation adds it to the normalized
+        // index below
+        return;
+      },
+      None => { self.reverse_entries.insert(translation.clone(), vec![steno.clone()]); },
+    }
+
+    let normalized = normalize_translation(translation);
+    match self.normalized_reverse_entries.get_mut(normalized.as_str()) {
+      Some(translations) => translations.push(translation.clone()),
+      None => {
+        // Most translations are already in normalized form, so share them
+        let key = if normalized == **translation { translation.clone() } else { Arc::from(normalized) };
+        self.normalized_reverse_entries.insert(key, vec![translation.clone()]);
       },
-    );
+    }
+  }
 
-    let key_length = steno.chars().filter(|c| *c == '/').count() + 1;
-    if key_length > self.longest_key {
-      self.longest_key = key_length;
+  // Reuse the copy of `translation` already held by another entry, if any
+  fn intern_translation(&self, translation: String) -> Arc<str> {
+    match self.reverse_entries.get_key_value(translation.as_str()) {
+      Some((existing, _)) => existing.clone(),
+      None => Arc::from(translation),
     }
+  }
 
-    if let Some(set) = self.reverse_entries.get_mut(&translation) {
-      set.insert(steno);
-    } else {
-      let mut set = HashSet::new();
-      set.insert(steno);
-      self.reverse_entries.insert(translation, set);
+  pub fn add_entry(&mut self, steno: String, translation: String, comment: Option<String>) {
+    let steno = match self.normalize {
+      true => self.outline(&steno).into_owned(),
+      false => steno,
+    };
+    self.add_exact(steno, translation, comment);
+  }
+
+  fn add_exact(&mut self, steno: String, translation: String, comment: Option<String>) {
+    if !self.history.is_recording() {
+      return self.insert_entry(steno, translation, comment);
     }
+
+    let before = self.positioned_entry(&steno);
+    self.insert_entry(steno.clone(), translation, comment);
+    self.record(steno, before);
+  }
+
+  fn insert_entry(&mut self, steno: String, translation: String, comment: Option<String>) {
+    let steno: Arc<str> = match self.entries.get(steno.as_str()) {
+      Some(entry) => {
+        // This outline is already defined and being overridden below, remove
+        // the corresponding reverse entry so we don't inadvertently return that
+        // on reverse lookups
+        let (existing, old_translation) = (entry.steno.clone(), entry.translation.clone());
+        self.remove_reverse_entry(&existing, &old_translation);
+        existing
+      },
+      None => {
+        self.count_strokes(&steno, true);
+        Arc::from(steno)
+      },
+    };
+
+    let translation = self.intern_translation(translation);
+    let entry = Entry {
+      steno: steno.clone(),
+      translation: translation.clone(),
+      metadata: comment.map(|comment| EntryMetadata { comment: Some(comment) }),
+    };
+    self.entries.insert(steno.clone(), entry);
+    if let Some(positions) = &mut self.positions {
+      // Redefined outlines are moved to the end of the file too
+      positions.remove(&steno);
+      positions.push(steno.clone());
+    }
+
+    if let Some(trie) = &mut self.trie {
+      trie.insert(&steno);
+    }
+
+    self.add_reverse_entry(&steno, &translation);
   }
 
   pub fn remove_entry(&mut self, steno: String) {
-    if let Some(entry) = self.entries.get(&steno) {
-      if let Some(rev_entry) = self.reverse_entries.get_mut(&entry.translation) {
-        rev_entry.remove(&steno);
+    let steno = match self.normalize {
+      true => self.outline(&steno).into_owned(),
+      false => steno,
+    };
+    self.remove_exact(steno);
+  }
 
-        if rev_entry.is_empty() {
-          self.reverse_entries.remove(&entry.translation);
-        }
+  fn remove_exact(&mut self, steno: String) {
+    if !self.history.is_recording() {
+      return self.delete_entry(&steno);
+    }
+
+    if let Some(before) = self.positioned_entry(&steno) {
+      self.delete_entry(&steno);
+      self.record(steno, Some(before));
+    }
+  }
+
+  fn delete_entry(&mut self, steno: &str) {
+    if let Some(entry) = self.entries.remove(steno) {
+      self.remove_reverse_entry(steno, &entry.translation);
+      self.count_strokes(steno, false);
+      if let Some(positions) = &mut self.positions {
+        positions.remove(steno);
+      }
+
+      if let Some(trie) = &mut self.trie {
+        trie.remove(steno);
+      }
+    }
+  }
+
+  /// Set or remove the comment on the entry for `steno`, returning false if
+  /// the outline is not defined.
+  pub fn set_comment(&mut self, steno: &str, comment: Option<&str>) -> bool {
+    let steno = &*self.outline(steno);
+    let before = match self.history.is_recording() {
+      true => self.positioned_entry(steno),
+      false => None,
+    };
+    let entry = match self.entries.get_mut(steno) {
+      Some(entry) => entry,
+      None => return false,
+    };
+
+    match comment {
+      Some(comment) => entry.add_comment(comment),
+      None => entry.remove_comment(),
+    }
+    if before.is_some() {
+      self.record(steno.to_string(), before);
+    }
+    true
+  }
+
+  /// Change the translation of the entry for `steno` without moving it in
+  /// the file, returning false if the outline is not defined.
+  pub fn set_translation(&mut self, steno: &str, translation: String) -> bool {
+    let steno = &*self.outline(steno);
+    let before = match self.history.is_recording() {
+      true => self.positioned_entry(steno),
+      false => None,
+    };
+    let (existing, old_translation) = match self.entries.get(steno) {
+      Some(entry) => (entry.steno.clone(), entry.translation.clone()),
+      None => return false,
+    };
+
+    self.remove_reverse_entry(&existing, &old_translation);
+    let translation = self.intern_translation(translation);
+    self.entries.get_mut(steno).unwrap().translation = translation.clone();
+    self.add_reverse_entry(&existing, &translation);
+    if before.is_some() {
+      self.record(steno.to_string(), before);
+    }
+    true
+  }
+
+  // The entry for `steno` along with its position in the file
+  fn positioned_entry(&mut self, steno: &str) -> Option<(usize, Entry)> {
+    let entry = self.entries.get(steno)?.clone();
+    if self.positions.is_none() {
+      // Entries just added at the end don't need the index
+      if self.entries.back().map_or(false, |(last, _)| &**last == steno) {
+        return Some((self.entries.len() - 1, entry));
+      }
+      self.positions = Some(Positions::new(self.entries.keys()));
+    }
+    Some((self.positions.as_ref()?.get(steno)?, entry))
+  }
+
+  fn record(&mut self, steno: String, before: Option<(usize, Entry)>) {
+    let after = self.positioned_entry(&steno);
+    self.history.record(Edit { steno, before, after });
+  }
+
+  // Put an outline back the way it was before or after an edit, without
+  // recording it as a new edit. Outlines still defined keep their place in
+  // the file, and outlines defined again are added at the end.
+  fn restore_entry(&mut self, steno: &str, entry: Option<&Entry>) {
+    let entry = match entry {
+      Some(entry) => entry.clone(),
+      None => return self.delete_entry(steno),
+    };
+    let (existing, old_translation) = match self.entries.get(steno) {
+      Some(existing) => (existing.steno.clone(), existing.translation.clone()),
+      None => {
+        let comment = entry.comment();
+        return self.insert_entry(entry.steno.to_string(), entry.translation.to_string(), comment);
+      },
+    };
+
+    if old_translation != entry.translation {
+      self.remove_reverse_entry(steno, &old_translation);
+      self.add_reverse_entry(&existing, &entry.translation);
+    }
+    *self.entries.get_mut(steno).unwrap() = Entry { steno: existing, ..entry };
+  }
+
+  // Undo or redo `edits`, each given as the outline and its positioned entry
+  // before and after the change, in the order they are to be restored. The
+  // moves are replayed on a list of the outlines, and the file is put in that
+  // order once at the end.
+  fn restore<'a>(&mut self, edits: impl Iterator<Item = (&'a str, &'a Option<(usize, Entry)>,
+      &'a Option<(usize, Entry)>)>) {
+    let mut order: Vec<Arc<str>> = self.entries.keys().cloned().collect();
+    for (steno, from, to) in edits {
+      // The outline is where the edit left it, unless it was changed outside
+      // a transaction since
+      let current = match from {
+        Some((position, _)) if order.get(*position).map_or(false, |o| &**o == steno) =>
+          Some(*position),
+        _ if self.entries.contains_key(steno) => order.iter().position(|o| &**o == steno),
+        _ => None,
+      };
+      if let Some(position) = current {
+        order.remove(position);
+      }
+      if let Some((position, entry)) = to {
+        order.insert((*position).min(order.len()), entry.steno.clone());
+      }
+      self.restore_entry(steno, to.as_ref().map(|(_, entry)| entry));
+    }
+
+    for outline in order {
+      self.entries.get_refresh(&outline);
+    }
+    self.positions = None;
+  }
+
+  fn revert(&mut self, changeset: &Changeset) {
+    self.restore(changeset.edits.iter().rev()
+      .map(|edit| (edit.steno.as_str(), &edit.after, &edit.before)));
+  }
+
+  fn reapply(&mut self, changeset: &Changeset) {
+    self.restore(changeset.edits.iter()
+      .map(|edit| (edit.steno.as_str(), &edit.before, &edit.after)));
+  }
+
+  /// Start a transaction. Edits made until the matching `commit` or
+  /// `rollback` are applied immediately, but can be rolled back together.
+  /// Transactions can be nested; only the outermost one can be undone.
+  /// Rolling back or undoing puts entries back where they were in the file.
+  ///
+  /// Only edits made with `add_entry`, `remove_entry`, `set_translation` and
+  /// `set_comment` are recorded. Edits made outside a transaction, through
+  /// `entry_mut`, or by changing the public fields directly cannot be rolled
+  /// back or undone.
+  pub fn begin(&mut self) {
+    self.history.begin();
+  }
+
+  pub fn commit(&mut self) -> Result<(), Error> {
+    self.history.commit()?;
+    if !self.history.is_recording() {
+      self.positions = None;
+    }
+    Ok(())
+  }
+
+  /// Revert the edits made since the innermost open transaction began.
+  pub fn rollback(&mut self) -> Result<(), Error> {
+    let changeset = self.history.rollback()?;
+    self.revert(&changeset);
+    Ok(())
+  }
+
+  /// Run `edit` in a transaction, committing it if `edit` succeeds and
+  /// rolling it back if it fails. `edit` cannot commit or roll back the
+  /// transaction itself; trying to fails with `Error::NoTransaction`.
+  pub fn transaction<T, E>(&mut self, edit: impl FnOnce(&mut Self) -> Result<T, E>)
+      -> Result<T, E> {
+    self.run_transaction(edit, Self::commit)
+  }
+
+  /// Run `edit` like `transaction`, but keep nothing to undo once it
+  /// succeeds, for edits that only need to be made all at once.
+  pub(crate) fn atomically<T, E>(&mut self, edit: impl FnOnce(&mut Self) -> Result<T, E>)
+      -> Result<T, E> {
+    self.run_transaction(edit, |dict| {
+      dict.history.forget()?;
+      if !dict.history.is_recording() {
+        dict.positions = None;
       }
-      self.entries.remove(&steno);
+      Ok(())
+    })
+  }
+
+  fn run_transaction<T, E>(&mut self, edit: impl FnOnce(&mut Self) -> Result<T, E>,
+      close: impl FnOnce(&mut Self) -> Result<(), Error>) -> Result<T, E> {
+    self.begin();
+    let previous = self.history.lock();
+    let result = edit(self);
+    self.history.unlock(previous);
+    // The transaction is still open, since `edit` could not close it
+    let _ = match result {
+      Ok(_) => close(self),
+      Err(_) => self.rollback(),
+    };
+    result
+  }
+
+  /// Undo the last committed transaction, returning false if there is
+  /// nothing to undo.
+  pub fn undo(&mut self) -> Result<bool, Error> {
+    match self.history.pop_undo()? {
+      Some(changeset) => {
+        self.revert(&changeset);
+        self.history.push_redo(changeset);
+        Ok(true)
+      },
+      None => Ok(false),
     }
   }
 
+  /// Redo the last undone transaction, returning false if there is nothing
+  /// to redo.
+  pub fn redo(&mut self) -> Result<bool, Error> {
+    match self.history.pop_redo()? {
+      Some(changeset) => {
+        self.reapply(&changeset);
+        self.history.push_undo(changeset);
+        Ok(true)
+      },
+      None => Ok(false),
+    }
+  }
+
+  pub fn can_undo(&self) -> bool {
+    self.history.can_undo()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    self.history.can_redo()
+  }
+
   pub fn contains_key(&self, steno: &str) -> bool {
-    self.entries.contains_key(steno)
+    self.entries.contains_key(&*self.outline(steno))
   }
 
   pub fn lookup(&self, steno: &str) -> Option<String> {
-    Some(self.entries.get(steno)?.translation.clone())
+    Some(self.entries.get(&*self.outline(steno))?.translation.to_string())
+  }
+
+  /// The outline `steno` is stored under. Once outlines are normalized, this
+  /// is the canonical form of the outline in the dictionary's steno system,
+  /// without whitespace; outlines that are not valid steno only have their
+  /// whitespace removed.
+  pub fn outline<'a>(&self, steno: &'a str) -> Cow<'a, str> {
+    if !self.normalize {
+      return Cow::Borrowed(steno);
+    }
+    let stripped: String = steno.chars().filter(|c| !c.is_whitespace()).collect();
+    match self.steno_system.parse_outline(&stripped) {
+      Ok(strokes) => Cow::Owned(self.steno_system.format_outline(&strokes)),
+      Err(_) => Cow::Owned(stripped),
+    }
+  }
+
+  pub fn normalizes_outlines(&self) -> bool {
+    self.normalize
+  }
+
+  /// Store outlines in canonical form from now on, so that outlines written
+  /// differently but meaning the same strokes, such as `KA-T` and `KAT`, are
+  /// the same entry. Outlines passed to `add_entry`, `lookup` and the other
+  /// methods that take an outline are normalized too. The outlines already in
+  /// the dictionary are rewritten in file order, and returned are the entries
+  /// that were merged because their outlines became the same. Call this again
+  /// after changing the steno system.
+  pub fn normalize_outlines(&mut self) -> Vec<MergedOutline> {
+    self.normalize = true;
+    let entries: Vec<Entry> = self.entries.values().cloned().collect();
+    if entries.iter().all(|entry| *self.outline(&entry.steno) == *entry.steno) {
+      return vec![];
+    }
+
+    for entry in &entries {
+      self.remove_exact(entry.steno.to_string());
+    }
+    let mut added: HashMap<String, Entry> = HashMap::new();
+    let mut merged = vec![];
+    for entry in entries {
+      let steno = self.outline(&entry.steno).into_owned();
+      if let Some(replaced) = added.insert(steno.clone(), entry.clone()) {
+        merged.push(MergedOutline { steno: steno.clone(), replaced, kept: entry.clone() });
+      }
+      self.add_exact(steno, entry.translation.to_string(), entry.comment());
+    }
+    merged
   }
 
   pub fn rev_lookup(&self, translation: &str) -> Option<Vec<String>> {
-    Some(self.reverse_entries.get(translation)?.into_iter().cloned().collect())
+    Some(self.reverse_entries.get(translation)?.iter().map(|steno| steno.to_string()).collect())
+  }
+
+  /// The translations that differ from `translation` only in case, including
+  /// `translation` itself if it is defined.
+  pub fn casereverse_lookup(&self, translation: &str) -> Vec<String> {
+    let folded = translation.to_lowercase();
+    match self.normalized_reverse_entries.get(normalize_translation(translation).as_str()) {
+      Some(translations) => translations.iter()
+        .filter(|tl| tl.to_lowercase() == folded)
+        .map(|tl| tl.to_string())
+        .collect(),
+      None => vec![],
+    }
+  }
+
+  /// The outlines whose translations match `translation` once case and
+  /// formatting operators are ignored, so `Court` finds `{-|}court` and
+  /// `{^ing}` finds `{^}ing`.
+  pub fn normalized_rev_lookup(&self, translation: &str) -> Vec<String> {
+    let normalized = normalize_translation(translation);
+    if normalized.is_empty() {
+      // Translations with no text of their own, like commands, would all
+      // match each other
+      return vec![];
+    }
+
+    match self.normalized_reverse_entries.get(normalized.as_str()) {
+      Some(translations) => translations.iter()
+        .filter_map(|tl| self.reverse_entries.get(tl))
+        .flat_map(|outlines| outlines.iter().map(|steno| steno.to_string()))
+        .collect(),
+      None => vec![],
+    }
+  }
+
+  /// All entries whose `field` matches `pattern`, in file order.
+  pub fn search(&self, pattern: &Pattern, field: SearchField) -> Vec<&Entry> {
+    self.entries.values()
+      .filter(|entry| pattern.matches_entry(entry, field))
+      .collect()
+  }
+
+  /// All entries whose outlines are not valid steno in the dictionary's steno
+  /// system, such as outlines from a
+  /// CAT export with keys out of steno order, each with the reason, in file
+  /// order.
+  pub fn invalid_outlines(&self) -> Vec<(&Entry, Error)> {
+    self.entries.values()
+      .filter_map(|entry| self.steno_system.parse_outline(&entry.steno).err().map(|err| (entry, err)))
+      .collect()
+  }
+
+  /// All entries with a key combination that is not valid Plover syntax,
+  /// such as `{#control(c}`, each with the reason, in file order.
+  pub fn invalid_key_combos(&self) -> Vec<(&Entry, Error)> {
+    self.entries.values()
+      .filter_map(|entry| validate_key_combos(&entry.translation).err().map(|err| (entry, err)))
+      .collect()
+  }
+
+  /// All outlines starting with the strokes in `prefix`, including `prefix`
+  /// itself, ordered stroke by stroke.
+  pub fn outlines_with_prefix(&self, prefix: &str) -> Vec<String> {
+    if let Some(trie) = &self.trie {
+      return trie.with_prefix(prefix);
+    }
+
+    let prefix = split_strokes(prefix);
+    let mut outlines: Vec<Vec<&str>> = self.entries.keys()
+      .map(|steno| split_strokes(steno))
+      .filter(|strokes| strokes.starts_with(&prefix))
+      .collect();
+    outlines.sort();
+    outlines.into_iter().map(|strokes| strokes.join("/")).collect()
+  }
+
+  /// Whether any outline is longer than `prefix` and starts with it.
+  pub fn has_continuation(&self, prefix: &str) -> bool {
+    if let Some(trie) = &self.trie {
+      return trie.has_continuation(prefix);
+    }
+
+    let prefix = split_strokes(prefix);
+    self.entries.keys()
+      .map(|steno| split_strokes(steno))
+      .any(|strokes| strokes.len() > prefix.len() && strokes.starts_with(&prefix))
   }
 
   pub fn write(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
@@ -153,19 +709,81 @@ impl Dictionary {
     Ok(())
   }
 
+  /// Write the dictionary in the given dialect of RTF, returning the
+  /// constructs that could not be written as-is along with their outlines.
+  pub fn write_dialect(&self, writer: &mut dyn io::Write, dialect: Dialect)
+      -> Result<Vec<(String, DialectWarning)>, io::Error> {
+    self.write_with_options(writer, &WriteOptions { dialect, ..WriteOptions::default() })
+  }
+
+  /// Write the dictionary in the given order and dialect of RTF, with number
+  /// strokes written as the options say, returning the constructs that could
+  /// not be written as-is along with their outlines.
+  pub fn write_with_options(&self, writer: &mut dyn io::Write, options: &WriteOptions)
+      -> Result<Vec<(String, DialectWarning)>, io::Error> {
+    let mut warnings = vec![];
+    write!(writer, "{}", FILE_HEADER)?;
+    write!(writer, "{{\\*\\cxsystem {}}}", &self.cre_system)?;
+    write!(writer, "{}", FILE_HEADER_END)?;
+    for entry in self.entries_in_order(options.order) {
+      let renamed;
+      let entry = match self.steno_system.write_numbers(&entry.steno, options.number_key) {
+        Cow::Borrowed(_) => entry,
+        Cow::Owned(steno) => {
+          renamed = Entry { steno: Arc::from(steno), ..entry.clone() };
+          &renamed
+        },
+      };
+      for warning in entry.write_dialect(writer, options.dialect)? {
+        warnings.push((entry.steno.to_string(), warning));
+      }
+    }
+    write!(writer, "{}", FILE_FOOTER)?;
+    Ok(warnings)
+  }
+
+  /// The entries of the dictionary in the given order.
+  pub fn entries_in_order(&self, order: Order) -> Vec<&Entry> {
+    let mut entries: Vec<&Entry> = self.entries.values().collect();
+    sort_entries(&mut entries, order, &self.steno_system);
+    entries
+  }
+
+  /// Write the given entries as a dictionary with this dictionary's system,
+  /// each with its translation already formatted as RTF.
+  pub(crate) fn write_formatted<'a>(&self, writer: &mut dyn io::Write,
+      entries: impl Iterator<Item = (&'a Entry, String)>) -> Result<(), io::Error> {
+    write!(writer, "{}", FILE_HEADER)?;
+    write!(writer, "{{\\*\\cxsystem {}}}", &self.cre_system)?;
+    write!(writer, "{}", FILE_HEADER_END)?;
+    for (entry, rtf) in entries {
+      entry.write_rtf(writer, &rtf)?;
+    }
+    write!(writer, "{}", FILE_FOOTER)?;
+    Ok(())
+  }
+
   pub fn len(&self) -> usize {
     self.entries.len()
   }
 
   pub fn entry(&self, steno: &str) -> Option<&Entry> {
-    Some(self.entries.get(steno)?)
+    self.entries.get(&*self.outline(steno))
   }
 
+  /// The entry for `steno`, to edit in place. Edits made this way are not
+  /// recorded by transactions; use `set_translation` and `set_comment` so
+  /// changes can be rolled back and undone.
   pub fn entry_mut(&mut self, steno: &str) -> Option<&mut Entry> {
-    Some(self.entries.get_mut(steno)?)
+    let steno = self.outline(steno);
+    self.entries.get_mut(&*steno)
   }
 }
 
+fn split_strokes(outline: &str) -> Vec<&str> {
+  outline.split('/').filter(|stroke| !stroke.is_empty()).collect()
+}
+
 impl fmt::Debug for Dictionary {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     self.entries.values().cloned().collect::<Vec<_>>().fmt(f)
@@ -187,6 +805,6 @@ macro_rules! add_entry {
 #[macro_export]
 macro_rules! remove_entry {
   ($dict:expr, $outline:expr) => {
-    $dict.remove_entry(String::from($outline));
+    $dict.remove_entry(String::from($outline))
   };
 }
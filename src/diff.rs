@@ -0,0 +1,208 @@
+use serde_json::{Map, Value};
+
+use crate::dict::Dictionary;
+use crate::error::Error;
+
+/// A single difference between two dictionaries, by outline.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Change {
+  Added { steno: String, translation: String, comment: Option<String> },
+  Removed { steno: String, translation: String, comment: Option<String> },
+  Retranslated { steno: String, old: String, new: String },
+  Recommented { steno: String, old: Option<String>, new: Option<String> },
+}
+
+impl Change {
+  pub fn steno(&self) -> &str {
+    match self {
+      Self::Added { steno, .. } | Self::Removed { steno, .. } |
+      Self::Retranslated { steno, .. } | Self::Recommented { steno, .. } => steno,
+    }
+  }
+}
+
+/// A change in a patch that could not be applied because the dictionary no
+/// longer matches what the patch was made against.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Conflict {
+  pub change: Change,
+  /// The translation and comment currently in the dictionary, if any.
+  pub current: Option<(String, Option<String>)>,
+}
+
+/// Compare two dictionaries entry by entry, ignoring the order of entries
+/// and how they were written. Changes to existing outlines are listed in the
+/// order of `old`, followed by the outlines added in `new`.
+pub fn diff(old: &Dictionary, new: &Dictionary) -> Vec<Change> {
+  let mut changes = vec![];
+  for (steno, old_entry) in &old.entries {
+    match new.entry(steno) {
+      None => changes.push(Change::Removed {
+        steno: steno.to_string(),
+        translation: old_entry.translation.to_string(),
+        comment: old_entry.comment(),
+      }),
+      Some(new_entry) => {
+        if old_entry.translation != new_entry.translation {
+          changes.push(Change::Retranslated {
+            steno: steno.to_string(),
+            old: old_entry.translation.to_string(),
+            new: new_entry.translation.to_string(),
+          });
+        }
+        if old_entry.comment() != new_entry.comment() {
+          changes.push(Change::Recommented {
+            steno: steno.to_string(),
+            old: old_entry.comment(),
+            new: new_entry.comment(),
+          });
+        }
+      },
+    }
+  }
+  for (steno, new_entry) in &new.entries {
+    if !old.contains_key(steno) {
+      changes.push(Change::Added {
+        steno: steno.to_string(),
+        translation: new_entry.translation.to_string(),
+        comment: new_entry.comment(),
+      });
+    }
+  }
+  changes
+}
+
+fn optional(value: &Option<String>) -> Value {
+  match value {
+    Some(s) => Value::String(s.clone()),
+    None => Value::Null,
+  }
+}
+
+fn change_to_json(change: &Change) -> Value {
+  let mut map = Map::new();
+  let mut set = |key: &str, value: Value| { map.insert(key.to_string(), value); };
+  match change {
+    Change::Added { steno, translation, comment } => {
+      set("op", Value::from("add"));
+      set("steno", Value::from(steno.as_str()));
+      set("translation", Value::from(translation.as_str()));
+      set("comment", optional(comment));
+    },
+    Change::Removed { steno, translation, comment } => {
+      set("op", Value::from("remove"));
+      set("steno", Value::from(steno.as_str()));
+      set("translation", Value::from(translation.as_str()));
+      set("comment", optional(comment));
+    },
+    Change::Retranslated { steno, old, new } => {
+      set("op", Value::from("retranslate"));
+      set("steno", Value::from(steno.as_str()));
+      set("old", Value::from(old.as_str()));
+      set("new", Value::from(new.as_str()));
+    },
+    Change::Recommented { steno, old, new } => {
+      set("op", Value::from("recomment"));
+      set("steno", Value::from(steno.as_str()));
+      set("old", optional(old));
+      set("new", optional(new));
+    },
+  }
+  Value::Object(map)
+}
+
+/// Write a list of changes as a JSON patch, one object per change.
+pub fn write_patch(changes: &[Change]) -> String {
+  let patch = Value::Array(changes.iter().map(change_to_json).collect());
+  serde_json::to_string_pretty(&patch).unwrap()
+}
+
+fn change_from_json(value: &Value) -> Result<Change, String> {
+  let map = value.as_object().ok_or("expected an object")?;
+  let string = |key: &str| match map.get(key) {
+    Some(Value::String(s)) => Ok(s.clone()),
+    _ => Err(format!("expected a string for {:?}", key)),
+  };
+  let optional = |key: &str| match map.get(key) {
+    Some(Value::String(s)) => Ok(Some(s.clone())),
+    Some(Value::Null) | None => Ok(None),
+    _ => Err(format!("expected a string or null for {:?}", key)),
+  };
+
+  let steno = string("steno")?;
+  match string("op")?.as_str() {
+    "add" => Ok(Change::Added {
+      steno, translation: string("translation")?, comment: optional("comment")?,
+    }),
+    "remove" => Ok(Change::Removed {
+      steno, translation: string("translation")?, comment: optional("comment")?,
+    }),
+    "retranslate" => Ok(Change::Retranslated {
+      steno, old: string("old")?, new: string("new")?,
+    }),
+    "recomment" => Ok(Change::Recommented {
+      steno, old: optional("old")?, new: optional("new")?,
+    }),
+    op => Err(format!("unknown operation {:?}", op)),
+  }
+}
+
+/// Read a JSON patch written by `write_patch`.
+pub fn parse_patch(patch: &str) -> Result<Vec<Change>, Error> {
+  let invalid = |reason: String| Error::InvalidPatch { reason };
+  match serde_json::from_str(patch) {
+    Ok(Value::Array(changes)) => changes.iter()
+      .enumerate()
+      .map(|(i, change)| change_from_json(change)
+        .map_err(|reason| invalid(format!("change {}: {}", i + 1, reason))))
+      .collect(),
+    Ok(_) => Err(invalid("expected a list of changes".to_string())),
+    Err(err) => Err(invalid(err.to_string())),
+  }
+}
+
+/// Apply a list of changes to a dictionary. Changes whose outline no longer
+/// has the translation or comment they expect are skipped and returned as
+/// conflicts; changes that have already been made are ignored.
+pub fn apply_patch(dict: &mut Dictionary, changes: &[Change]) -> Vec<Conflict> {
+  let mut conflicts = vec![];
+  for change in changes {
+    let current = dict.entry(change.steno())
+      .map(|entry| (entry.translation.to_string(), entry.comment()));
+    let current_translation = current.as_ref().map(|(tl, _)| tl.as_str());
+    let current_comment = current.as_ref().and_then(|(_, comment)| comment.as_deref());
+
+    let applies = match change {
+      Change::Added { translation, comment, .. } => match &current {
+        None => {
+          dict.add_entry(change.steno().to_string(), translation.clone(), comment.clone());
+          true
+        },
+        Some(current) => *current == (translation.clone(), comment.clone()),
+      },
+      Change::Removed { translation, .. } => match current_translation {
+        None => true,
+        Some(tl) if tl == translation => {
+          dict.remove_entry(change.steno().to_string());
+          true
+        },
+        Some(_) => false,
+      },
+      Change::Retranslated { old, new, .. } => match current_translation {
+        Some(tl) if tl == new => true,
+        Some(tl) if tl == old => dict.set_translation(change.steno(), new.clone()),
+        _ => false,
+      },
+      Change::Recommented { old, new, .. } => match current_translation {
+        Some(_) if current_comment == new.as_deref() => true,
+        Some(_) if current_comment == old.as_deref() => dict.set_comment(change.steno(), new.as_deref()),
+        _ => false,
+      },
+    };
+
+    if !applies {
+      conflicts.push(Conflict { change: change.clone(), current });
+    }
+  }
+  conflicts
+}
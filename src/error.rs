@@ -0,0 +1,40 @@
+use std::error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+  InvalidKeyCombo { combo: String, reason: String },
+  InvalidPattern { pattern: String, reason: String },
+  InvalidStroke { stroke: String, reason: String },
+  InvalidStenoSystem { reason: String },
+  InvalidPatch { reason: String },
+  InvalidResolution { reason: String },
+  InvalidCache { reason: String },
+  StaleCache,
+  NoTransaction,
+  TransactionInProgress,
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidKeyCombo { combo, reason } =>
+        write!(f, "invalid key combination {:?}: {}", combo, reason),
+      Self::InvalidPattern { pattern, reason } =>
+        write!(f, "invalid search pattern {:?}: {}", pattern, reason),
+      Self::InvalidStroke { stroke, reason } =>
+        write!(f, "invalid stroke {:?}: {}", stroke, reason),
+      Self::InvalidStenoSystem { reason } => write!(f, "invalid steno system: {}", reason),
+      Self::InvalidPatch { reason } => write!(f, "invalid patch: {}", reason),
+      Self::InvalidResolution { reason } =>
+        write!(f, "invalid resolution file: {}", reason),
+      Self::InvalidCache { reason } => write!(f, "invalid dictionary cache: {}", reason),
+      Self::StaleCache => write!(f, "dictionary cache is out of date"),
+      Self::NoTransaction => write!(f, "no transaction in progress"),
+      Self::TransactionInProgress =>
+        write!(f, "cannot undo or redo while a transaction is in progress"),
+    }
+  }
+}
+
+impl error::Error for Error {}
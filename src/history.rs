@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::dict::Entry;
+use crate::error::Error;
+
+/// A change to a single outline, as the entry and its position in the file
+/// before and after it.
+#[derive(Clone, Debug)]
+pub(crate) struct Edit {
+  pub steno: String,
+  pub before: Option<(usize, Entry)>,
+  pub after: Option<(usize, Entry)>,
+}
+
+/// The edits made in a transaction, in order.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Changeset {
+  pub edits: Vec<Edit>,
+}
+
+/// Open transactions, innermost last, and the committed transactions that
+/// can be undone and redone.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct History {
+  open: Vec<Changeset>,
+  /// The number of open transactions that belong to `Dictionary::transaction`
+  /// and can only be closed by it.
+  locked: usize,
+  undo: Vec<Changeset>,
+  redo: Vec<Changeset>,
+}
+
+impl History {
+  pub fn is_recording(&self) -> bool {
+    !self.open.is_empty()
+  }
+
+  pub fn record(&mut self, edit: Edit) {
+    if let Some(changeset) = self.open.last_mut() {
+      changeset.edits.push(edit);
+    }
+  }
+
+  pub fn begin(&mut self) {
+    self.open.push(Changeset::default());
+  }
+
+  /// Keep the open transactions from being closed until `unlock`, returning
+  /// the previous lock to restore.
+  pub fn lock(&mut self) -> usize {
+    std::mem::replace(&mut self.locked, self.open.len())
+  }
+
+  pub fn unlock(&mut self, previous: usize) {
+    self.locked = previous;
+  }
+
+  fn close(&mut self) -> Result<Changeset, Error> {
+    if self.open.len() <= self.locked {
+      return Err(Error::NoTransaction);
+    }
+    self.open.pop().ok_or(Error::NoTransaction)
+  }
+
+  /// Close the innermost transaction, folding its edits into the enclosing
+  /// one, or making it undoable if there is none.
+  pub fn commit(&mut self) -> Result<(), Error> {
+    let mut changeset = self.close()?;
+    match self.open.last_mut() {
+      Some(parent) => parent.edits.append(&mut changeset.edits),
+      None if changeset.edits.is_empty() => {},
+      None => {
+        self.undo.push(changeset);
+        self.redo.clear();
+      },
+    }
+    Ok(())
+  }
+
+  /// Close the innermost transaction, folding its edits into the enclosing
+  /// one, or dropping them if there is none so they can't be undone.
+  pub fn forget(&mut self) -> Result<(), Error> {
+    let mut changeset = self.close()?;
+    match self.open.last_mut() {
+      Some(parent) => parent.edits.append(&mut changeset.edits),
+      None if changeset.edits.is_empty() => {},
+      None => self.redo.clear(),
+    }
+    Ok(())
+  }
+
+  /// Close the innermost transaction, returning its edits to be reverted.
+  pub fn rollback(&mut self) -> Result<Changeset, Error> {
+    self.close()
+  }
+
+  pub fn can_undo(&self) -> bool {
+    !self.undo.is_empty()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    !self.redo.is_empty()
+  }
+
+  pub fn pop_undo(&mut self) -> Result<Option<Changeset>, Error> {
+    if self.is_recording() {
+      return Err(Error::TransactionInProgress);
+    }
+    Ok(self.undo.pop())
+  }
+
+  pub fn pop_redo(&mut self) -> Result<Option<Changeset>, Error> {
+    if self.is_recording() {
+      return Err(Error::TransactionInProgress);
+    }
+    Ok(self.redo.pop())
+  }
+
+  pub fn push_undo(&mut self, changeset: Changeset) {
+    self.undo.push(changeset);
+  }
+
+  pub fn push_redo(&mut self, changeset: Changeset) {
+    self.redo.push(changeset);
+  }
+}
+
+/// The position of each outline in the file, kept while a transaction is open
+/// so edits can record where their entries were without scanning the file.
+/// Outlines are numbered in the order they were added at the end of the file,
+/// and the position of an outline is the number of outlines still in the file
+/// with a lower number, counted with a Fenwick tree.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Positions {
+  numbers: HashMap<Arc<str>, usize>,
+  /// Node `i`, stored at `i - 1`, counts the outlines numbered from
+  /// `i - lowest_bit(i) + 1` to `i` that are still in the file.
+  tree: Vec<usize>,
+}
+
+fn lowest_bit(i: usize) -> usize {
+  i & i.wrapping_neg()
+}
+
+impl Positions {
+  pub fn new<'a>(outlines: impl Iterator<Item = &'a Arc<str>>) -> Self {
+    let mut positions = Self::default();
+    for outline in outlines {
+      positions.push(outline.clone());
+    }
+    positions
+  }
+
+  /// Add an outline at the end of the file.
+  pub fn push(&mut self, outline: Arc<str>) {
+    let i = self.tree.len() + 1;
+    let covered = self.count(i - 1) - self.count(i - lowest_bit(i));
+    self.tree.push(covered + 1);
+    self.numbers.insert(outline, i);
+  }
+
+  pub fn remove(&mut self, outline: &str) {
+    if let Some(mut i) = self.numbers.remove(outline) {
+      while i <= self.tree.len() {
+        self.tree[i - 1] -= 1;
+        i += lowest_bit(i);
+      }
+    }
+  }
+
+  pub fn get(&self, outline: &str) -> Option<usize> {
+    self.numbers.get(outline).map(|&i| self.count(i) - 1)
+  }
+
+  // The number of outlines numbered up to `i` still in the file
+  fn count(&self, mut i: usize) -> usize {
+    let mut count = 0;
+    while i > 0 {
+      count += self.tree[i - 1];
+      i -= lowest_bit(i);
+    }
+    count
+  }
+}
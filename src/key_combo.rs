@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use nom::IResult;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{multispace0, multispace1};
+use nom::combinator::opt;
+use nom::multi::separated_list0;
+use nom::sequence::{delimited, preceded, tuple};
+
+use crate::error::Error;
+use crate::key_names;
+use crate::translation::parse_translation;
+use crate::translation_model::Object;
+
+lazy_static! {
+  // The key names Plover accepts in {#...}, all lowercase since Plover
+  // matches them case-insensitively: the keysym names, along with the
+  // unsided modifier names Plover adds as aliases for the left-hand ones
+  static ref KEY_NAMES: HashSet<&'static str> = key_names::KEY_NAMES.iter()
+    .copied()
+    .chain(["alt", "control", "shift", "super"])
+    .collect();
+}
+
+/// A single key press in a key combination. `Hold` presses a key, presses
+/// the inner keys, then releases it, as in `shift(a b)`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum KeyPress {
+  Key(String),
+  Hold(String, Vec<KeyPress>),
+}
+
+/// A sequence of key presses, as in `{#control(c) Return}`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct KeyCombo(pub Vec<KeyPress>);
+
+fn key_name(input: &str) -> IResult<&str, &str> {
+  take_while1(|c: char| !c.is_whitespace() && c != '(' && c != ')')(input)
+}
+
+fn key_press(input: &str) -> IResult<&str, KeyPress> {
+  let (input, (name, held)) = tuple((
+    key_name,
+    opt(preceded(multispace0, delimited(tag("("), key_presses, tag(")"))))))(input)?;
+  let name = name.to_lowercase();
+  Ok((input, match held {
+    Some(keys) => KeyPress::Hold(name, keys),
+    None => KeyPress::Key(name),
+  }))
+}
+
+fn key_presses(input: &str) -> IResult<&str, Vec<KeyPress>> {
+  delimited(multispace0, separated_list0(multispace1, key_press), multispace0)(input)
+}
+
+fn unknown_key(keys: &[KeyPress]) -> Option<&str> {
+  keys.iter().find_map(|key| match key {
+    KeyPress::Key(name) if !KEY_NAMES.contains(name.as_str()) => Some(name.as_str()),
+    KeyPress::Key(_) => None,
+    KeyPress::Hold(name, _) if !KEY_NAMES.contains(name.as_str()) => Some(name.as_str()),
+    KeyPress::Hold(_, inner) => unknown_key(inner),
+  })
+}
+
+impl KeyCombo {
+  pub fn parse(combo: &str) -> Result<Self, Error> {
+    let invalid = |reason: String| Error::InvalidKeyCombo {
+      combo: combo.to_string(),
+      reason,
+    };
+
+    let mut depth = 0;
+    for c in combo.chars() {
+      match c {
+        '(' => depth += 1,
+        ')' if depth == 0 => return Err(invalid("unbalanced parentheses".to_string())),
+        ')' => depth -= 1,
+        _ => {},
+      }
+    }
+    if depth != 0 {
+      return Err(invalid("unbalanced parentheses".to_string()));
+    }
+
+    let keys = match key_presses(combo) {
+      Ok(("", keys)) => keys,
+      Ok((rest, _)) => return Err(invalid(format!("unexpected {:?}", rest))),
+      Err(_) => return Err(invalid("could not parse".to_string())),
+    };
+    if keys.is_empty() {
+      return Err(invalid("no keys given".to_string()));
+    }
+    if let Some(name) = unknown_key(&keys) {
+      return Err(invalid(format!("unknown key {:?}", name)));
+    }
+    Ok(Self(keys))
+  }
+}
+
+fn join(keys: &[KeyPress]) -> String {
+  keys.iter()
+    .map(|key| key.to_string())
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+impl fmt::Display for KeyPress {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Key(name) => write!(f, "{}", name),
+      Self::Hold(name, keys) => write!(f, "{}({})", name, join(keys)),
+    }
+  }
+}
+
+/// Written in canonical form: lowercase key names separated by single spaces.
+impl fmt::Display for KeyCombo {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", join(&self.0))
+  }
+}
+
+/// Check every key combination in a Plover translation.
+pub fn validate_key_combos(tl: &str) -> Result<(), Error> {
+  for obj in parse_translation(tl) {
+    if let Object::KeyCombo(combo) = obj {
+      KeyCombo::parse(&combo)?;
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,463 @@
+// Generated from X11's keysymdef.h and XF86keysym.h, the keysym tables
+// Plover's key names come from: every keysym name, lowercased since Plover
+// matches key names case-insensitively, with the XF86 prefix removed.
+// Regenerate with:
+//
+//   sed -nE 's/^#define (XK|XF86XK)_([A-Za-z0-9_]+)[[:space:]]+(0x|_EVDEVK).*/\2/p' \
+//     /usr/include/X11/keysymdef.h /usr/include/X11/XF86keysym.h \
+//     | tr A-Z a-z | sort -u
+
+pub static KEY_NAMES: &[&str] = &[
+  "0", "1", "10channelsdown", "10channelsup", "2", "3", "3270_altcursor",
+  "3270_attn", "3270_backtab", "3270_changescreen", "3270_copy",
+  "3270_cursorblink", "3270_cursorselect", "3270_deleteword", "3270_duplicate",
+  "3270_enter", "3270_eraseeof", "3270_eraseinput", "3270_exselect",
+  "3270_fieldmark", "3270_ident", "3270_jump", "3270_keyclick", "3270_left2",
+  "3270_pa1", "3270_pa2", "3270_pa3", "3270_play", "3270_printscreen",
+  "3270_quit", "3270_record", "3270_reset", "3270_right2", "3270_rule",
+  "3270_setup", "3270_test", "3dmode", "4", "5", "6", "7", "8", "9", "a",
+  "aacute", "abelowdot", "abovedot", "abreve", "abreveacute", "abrevebelowdot",
+  "abrevegrave", "abrevehook", "abrevetilde", "accessx_enable",
+  "accessx_feedback_enable", "acircumflex", "acircumflexacute",
+  "acircumflexbelowdot", "acircumflexgrave", "acircumflexhook",
+  "acircumflextilde", "acute", "addfavorite", "addressbook", "adiaeresis",
+  "ae", "agrave", "ahook", "alstoggle", "alt_l", "alt_r", "amacron",
+  "ampersand", "aogonek", "apostrophe", "applicationleft", "applicationright",
+  "approxeq", "approximate", "appselect", "arabic_0", "arabic_1", "arabic_2",
+  "arabic_3", "arabic_4", "arabic_5", "arabic_6", "arabic_7", "arabic_8",
+  "arabic_9", "arabic_ain", "arabic_alef", "arabic_alefmaksura", "arabic_beh",
+  "arabic_comma", "arabic_dad", "arabic_dal", "arabic_damma",
+  "arabic_dammatan", "arabic_ddal", "arabic_farsi_yeh", "arabic_fatha",
+  "arabic_fathatan", "arabic_feh", "arabic_fullstop", "arabic_gaf",
+  "arabic_ghain", "arabic_ha", "arabic_hah", "arabic_hamza",
+  "arabic_hamza_above", "arabic_hamza_below", "arabic_hamzaonalef",
+  "arabic_hamzaonwaw", "arabic_hamzaonyeh", "arabic_hamzaunderalef",
+  "arabic_heh", "arabic_heh_doachashmee", "arabic_heh_goal", "arabic_jeem",
+  "arabic_jeh", "arabic_kaf", "arabic_kasra", "arabic_kasratan",
+  "arabic_keheh", "arabic_khah", "arabic_lam", "arabic_madda_above",
+  "arabic_maddaonalef", "arabic_meem", "arabic_noon", "arabic_noon_ghunna",
+  "arabic_peh", "arabic_percent", "arabic_qaf", "arabic_question_mark",
+  "arabic_ra", "arabic_rreh", "arabic_sad", "arabic_seen", "arabic_semicolon",
+  "arabic_shadda", "arabic_sheen", "arabic_sukun", "arabic_superscript_alef",
+  "arabic_switch", "arabic_tah", "arabic_tatweel", "arabic_tcheh",
+  "arabic_teh", "arabic_tehmarbuta", "arabic_thal", "arabic_theh",
+  "arabic_tteh", "arabic_veh", "arabic_waw", "arabic_yeh", "arabic_yeh_baree",
+  "arabic_zah", "arabic_zain", "aring", "armenian_accent", "armenian_amanak",
+  "armenian_apostrophe", "armenian_at", "armenian_ayb", "armenian_ben",
+  "armenian_but", "armenian_cha", "armenian_da", "armenian_dza", "armenian_e",
+  "armenian_exclam", "armenian_fe", "armenian_full_stop", "armenian_ghat",
+  "armenian_gim", "armenian_hi", "armenian_ho", "armenian_hyphen",
+  "armenian_ini", "armenian_je", "armenian_ke", "armenian_ken", "armenian_khe",
+  "armenian_ligature_ew", "armenian_lyun", "armenian_men", "armenian_nu",
+  "armenian_o", "armenian_paruyk", "armenian_pe", "armenian_pyur",
+  "armenian_question", "armenian_ra", "armenian_re", "armenian_se",
+  "armenian_separation_mark", "armenian_sha", "armenian_shesht",
+  "armenian_tche", "armenian_to", "armenian_tsa", "armenian_tso",
+  "armenian_tyun", "armenian_verjaket", "armenian_vev", "armenian_vo",
+  "armenian_vyun", "armenian_yech", "armenian_yentamna", "armenian_za",
+  "armenian_zhe", "asciicircum", "asciitilde", "aspectratio", "assistant",
+  "asterisk", "at", "atilde", "attendantoff", "attendanton", "attendanttoggle",
+  "audiblebell_enable", "audio", "audiocycletrack", "audiodesc",
+  "audioforward", "audiolowervolume", "audiomedia", "audiomicmute",
+  "audiomute", "audionext", "audiopause", "audioplay", "audiopreset",
+  "audioprev", "audioraisevolume", "audiorandomplay", "audiorecord",
+  "audiorepeat", "audiorewind", "audiostop", "away", "b", "babovedot", "back",
+  "backforward", "backslash", "backspace", "ballotcross", "bar", "battery",
+  "because", "begin", "blank", "blue", "bluetooth", "book", "botintegral",
+  "botleftparens", "botleftsqbracket", "botleftsummation", "botrightparens",
+  "botrightsqbracket", "botrightsummation", "bott",
+  "botvertsummationconnector", "bouncekeys_enable", "braceleft", "braceright",
+  "bracketleft", "bracketright", "braille_blank", "braille_dot_1",
+  "braille_dot_10", "braille_dot_2", "braille_dot_3", "braille_dot_4",
+  "braille_dot_5", "braille_dot_6", "braille_dot_7", "braille_dot_8",
+  "braille_dot_9", "braille_dots_1", "braille_dots_12", "braille_dots_123",
+  "braille_dots_1234", "braille_dots_12345", "braille_dots_123456",
+  "braille_dots_1234567", "braille_dots_12345678", "braille_dots_1234568",
+  "braille_dots_123457", "braille_dots_1234578", "braille_dots_123458",
+  "braille_dots_12346", "braille_dots_123467", "braille_dots_1234678",
+  "braille_dots_123468", "braille_dots_12347", "braille_dots_123478",
+  "braille_dots_12348", "braille_dots_1235", "braille_dots_12356",
+  "braille_dots_123567", "braille_dots_1235678", "braille_dots_123568",
+  "braille_dots_12357", "braille_dots_123578", "braille_dots_12358",
+  "braille_dots_1236", "braille_dots_12367", "braille_dots_123678",
+  "braille_dots_12368", "braille_dots_1237", "braille_dots_12378",
+  "braille_dots_1238", "braille_dots_124", "braille_dots_1245",
+  "braille_dots_12456", "braille_dots_124567", "braille_dots_1245678",
+  "braille_dots_124568", "braille_dots_12457", "braille_dots_124578",
+  "braille_dots_12458", "braille_dots_1246", "braille_dots_12467",
+  "braille_dots_124678", "braille_dots_12468", "braille_dots_1247",
+  "braille_dots_12478", "braille_dots_1248", "braille_dots_125",
+  "braille_dots_1256", "braille_dots_12567", "braille_dots_125678",
+  "braille_dots_12568", "braille_dots_1257", "braille_dots_12578",
+  "braille_dots_1258", "braille_dots_126", "braille_dots_1267",
+  "braille_dots_12678", "braille_dots_1268", "braille_dots_127",
+  "braille_dots_1278", "braille_dots_128", "braille_dots_13",
+  "braille_dots_134", "braille_dots_1345", "braille_dots_13456",
+  "braille_dots_134567", "braille_dots_1345678", "braille_dots_134568",
+  "braille_dots_13457", "braille_dots_134578", "braille_dots_13458",
+  "braille_dots_1346", "braille_dots_13467", "braille_dots_134678",
+  "braille_dots_13468", "braille_dots_1347", "braille_dots_13478",
+  "braille_dots_1348", "braille_dots_135", "braille_dots_1356",
+  "braille_dots_13567", "braille_dots_135678", "braille_dots_13568",
+  "braille_dots_1357", "braille_dots_13578", "braille_dots_1358",
+  "braille_dots_136", "braille_dots_1367", "braille_dots_13678",
+  "braille_dots_1368", "braille_dots_137", "braille_dots_1378",
+  "braille_dots_138", "braille_dots_14", "braille_dots_145",
+  "braille_dots_1456", "braille_dots_14567", "braille_dots_145678",
+  "braille_dots_14568", "braille_dots_1457", "braille_dots_14578",
+  "braille_dots_1458", "braille_dots_146", "braille_dots_1467",
+  "braille_dots_14678", "braille_dots_1468", "braille_dots_147",
+  "braille_dots_1478", "braille_dots_148", "braille_dots_15",
+  "braille_dots_156", "braille_dots_1567", "braille_dots_15678",
+  "braille_dots_1568", "braille_dots_157", "braille_dots_1578",
+  "braille_dots_158", "braille_dots_16", "braille_dots_167",
+  "braille_dots_1678", "braille_dots_168", "braille_dots_17",
+  "braille_dots_178", "braille_dots_18", "braille_dots_2", "braille_dots_23",
+  "braille_dots_234", "braille_dots_2345", "braille_dots_23456",
+  "braille_dots_234567", "braille_dots_2345678", "braille_dots_234568",
+  "braille_dots_23457", "braille_dots_234578", "braille_dots_23458",
+  "braille_dots_2346", "braille_dots_23467", "braille_dots_234678",
+  "braille_dots_23468", "braille_dots_2347", "braille_dots_23478",
+  "braille_dots_2348", "braille_dots_235", "braille_dots_2356",
+  "braille_dots_23567", "braille_dots_235678", "braille_dots_23568",
+  "braille_dots_2357", "braille_dots_23578", "braille_dots_2358",
+  "braille_dots_236", "braille_dots_2367", "braille_dots_23678",
+  "braille_dots_2368", "braille_dots_237", "braille_dots_2378",
+  "braille_dots_238", "braille_dots_24", "braille_dots_245",
+  "braille_dots_2456", "braille_dots_24567", "braille_dots_245678",
+  "braille_dots_24568", "braille_dots_2457", "braille_dots_24578",
+  "braille_dots_2458", "braille_dots_246", "braille_dots_2467",
+  "braille_dots_24678", "braille_dots_2468", "braille_dots_247",
+  "braille_dots_2478", "braille_dots_248", "braille_dots_25",
+  "braille_dots_256", "braille_dots_2567", "braille_dots_25678",
+  "braille_dots_2568", "braille_dots_257", "braille_dots_2578",
+  "braille_dots_258", "braille_dots_26", "braille_dots_267",
+  "braille_dots_2678", "braille_dots_268", "braille_dots_27",
+  "braille_dots_278", "braille_dots_28", "braille_dots_3", "braille_dots_34",
+  "braille_dots_345", "braille_dots_3456", "braille_dots_34567",
+  "braille_dots_345678", "braille_dots_34568", "braille_dots_3457",
+  "braille_dots_34578", "braille_dots_3458", "braille_dots_346",
+  "braille_dots_3467", "braille_dots_34678", "braille_dots_3468",
+  "braille_dots_347", "braille_dots_3478", "braille_dots_348",
+  "braille_dots_35", "braille_dots_356", "braille_dots_3567",
+  "braille_dots_35678", "braille_dots_3568", "braille_dots_357",
+  "braille_dots_3578", "braille_dots_358", "braille_dots_36",
+  "braille_dots_367", "braille_dots_3678", "braille_dots_368",
+  "braille_dots_37", "braille_dots_378", "braille_dots_38", "braille_dots_4",
+  "braille_dots_45", "braille_dots_456", "braille_dots_4567",
+  "braille_dots_45678", "braille_dots_4568", "braille_dots_457",
+  "braille_dots_4578", "braille_dots_458", "braille_dots_46",
+  "braille_dots_467", "braille_dots_4678", "braille_dots_468",
+  "braille_dots_47", "braille_dots_478", "braille_dots_48", "braille_dots_5",
+  "braille_dots_56", "braille_dots_567", "braille_dots_5678",
+  "braille_dots_568", "braille_dots_57", "braille_dots_578", "braille_dots_58",
+  "braille_dots_6", "braille_dots_67", "braille_dots_678", "braille_dots_68",
+  "braille_dots_7", "braille_dots_78", "braille_dots_8", "break", "breve",
+  "brightnessadjust", "brightnessauto", "brightnessmax", "brightnessmin",
+  "brokenbar", "buttonconfig", "byelorussian_shortu", "c", "c_h", "cabovedot",
+  "cacute", "calculater", "calculator", "calendar", "cameradown",
+  "camerafocus", "cameraleft", "cameraright", "cameraup", "camerazoomin",
+  "camerazoomout", "cancel", "caps_lock", "careof", "caret", "caron", "ccaron",
+  "ccedilla", "ccircumflex", "cd", "cedilla", "cent", "ch", "channeldown",
+  "channelup", "checkerboard", "checkmark", "circle", "clear", "cleargrab",
+  "close", "club", "codeinput", "colon", "colonsign", "combining_acute",
+  "combining_belowdot", "combining_grave", "combining_hook", "combining_tilde",
+  "comma", "community", "containsas", "contextmenu", "contrastadjust",
+  "control_l", "control_r", "controlpanel", "copy", "copyright", "cr",
+  "crossinglines", "cruzeirosign", "cuberoot", "currency", "cursor", "cut",
+  "cycleangle", "cyrillic_a", "cyrillic_be", "cyrillic_che",
+  "cyrillic_che_descender", "cyrillic_che_vertstroke", "cyrillic_de",
+  "cyrillic_dzhe", "cyrillic_e", "cyrillic_ef", "cyrillic_el", "cyrillic_em",
+  "cyrillic_en", "cyrillic_en_descender", "cyrillic_er", "cyrillic_es",
+  "cyrillic_ghe", "cyrillic_ghe_bar", "cyrillic_ha", "cyrillic_ha_descender",
+  "cyrillic_hardsign", "cyrillic_i", "cyrillic_i_macron", "cyrillic_ie",
+  "cyrillic_io", "cyrillic_je", "cyrillic_ka", "cyrillic_ka_descender",
+  "cyrillic_ka_vertstroke", "cyrillic_lje", "cyrillic_nje", "cyrillic_o",
+  "cyrillic_o_bar", "cyrillic_pe", "cyrillic_schwa", "cyrillic_sha",
+  "cyrillic_shcha", "cyrillic_shha", "cyrillic_shorti", "cyrillic_softsign",
+  "cyrillic_te", "cyrillic_tse", "cyrillic_u", "cyrillic_u_macron",
+  "cyrillic_u_straight", "cyrillic_u_straight_bar", "cyrillic_ve",
+  "cyrillic_ya", "cyrillic_yeru", "cyrillic_yu", "cyrillic_ze", "cyrillic_zhe",
+  "cyrillic_zhe_descender", "d", "dabovedot", "dagger", "data", "database",
+  "dcaron", "dead_a", "dead_abovecomma", "dead_abovedot",
+  "dead_abovereversedcomma", "dead_abovering", "dead_aboveverticalline",
+  "dead_acute", "dead_belowbreve", "dead_belowcircumflex", "dead_belowcomma",
+  "dead_belowdiaeresis", "dead_belowdot", "dead_belowmacron", "dead_belowring",
+  "dead_belowtilde", "dead_belowverticalline", "dead_breve",
+  "dead_capital_schwa", "dead_caron", "dead_cedilla", "dead_circumflex",
+  "dead_currency", "dead_dasia", "dead_diaeresis", "dead_doubleacute",
+  "dead_doublegrave", "dead_e", "dead_grave", "dead_greek", "dead_hook",
+  "dead_horn", "dead_i", "dead_invertedbreve", "dead_iota",
+  "dead_longsolidusoverlay", "dead_lowline", "dead_macron", "dead_o",
+  "dead_ogonek", "dead_perispomeni", "dead_psili", "dead_semivoiced_sound",
+  "dead_small_schwa", "dead_stroke", "dead_tilde", "dead_u",
+  "dead_voiced_sound", "decimalpoint", "degree", "delete", "diaeresis",
+  "diamond", "dictate", "digitspace", "dintegral", "display", "displayoff",
+  "displaytoggle", "division", "documents", "dollar", "dongsign", "dos",
+  "doubbaselinedot", "doubleacute", "doubledagger", "doublelowquotemark",
+  "down", "downarrow", "downcaret", "downshoe", "downstile", "downtack",
+  "dstroke", "dvd", "e", "eabovedot", "eacute", "ebelowdot", "ecaron",
+  "ecircumflex", "ecircumflexacute", "ecircumflexbelowdot", "ecircumflexgrave",
+  "ecircumflexhook", "ecircumflextilde", "ecusign", "ediaeresis", "editor",
+  "egrave", "ehook", "eightsubscript", "eightsuperior", "eisu_shift",
+  "eisu_toggle", "eject", "elementof", "ellipsis", "em3space", "em4space",
+  "emacron", "emdash", "emfilledcircle", "emfilledrect", "emojipicker",
+  "emopencircle", "emopenrectangle", "emptyset", "emspace", "end", "endash",
+  "enfilledcircbullet", "enfilledsqbullet", "eng", "enopencircbullet",
+  "enopensquarebullet", "enspace", "eogonek", "equal", "escape", "eth",
+  "etilde", "eurosign", "excel", "exclam", "exclamdown", "execute", "explorer",
+  "ezh", "f", "f1", "f10", "f11", "f12", "f13", "f14", "f15", "f16", "f17",
+  "f18", "f19", "f2", "f20", "f21", "f22", "f23", "f24", "f25", "f26", "f27",
+  "f28", "f29", "f3", "f30", "f31", "f32", "f33", "f34", "f35", "f4", "f5",
+  "f6", "f7", "f8", "f9", "fabovedot", "farsi_0", "farsi_1", "farsi_2",
+  "farsi_3", "farsi_4", "farsi_5", "farsi_6", "farsi_7", "farsi_8", "farsi_9",
+  "farsi_yeh", "fastreverse", "favorites", "femalesymbol", "ff", "ffrancsign",
+  "figdash", "filledlefttribullet", "filledrectbullet", "filledrighttribullet",
+  "filledtribulletdown", "filledtribulletup", "finance", "find",
+  "first_virtual_screen", "fiveeighths", "fivesixths", "fivesubscript",
+  "fivesuperior", "fn", "fn_esc", "fnrightshift", "forward", "fourfifths",
+  "foursubscript", "foursuperior", "fourthroot", "frameback", "frameforward",
+  "fullscreen", "function", "g", "gabovedot", "game", "gbreve", "gcaron",
+  "gcedilla", "gcircumflex", "georgian_an", "georgian_ban", "georgian_can",
+  "georgian_char", "georgian_chin", "georgian_cil", "georgian_don",
+  "georgian_en", "georgian_fi", "georgian_gan", "georgian_ghan",
+  "georgian_hae", "georgian_har", "georgian_he", "georgian_hie",
+  "georgian_hoe", "georgian_in", "georgian_jhan", "georgian_jil",
+  "georgian_kan", "georgian_khar", "georgian_las", "georgian_man",
+  "georgian_nar", "georgian_on", "georgian_par", "georgian_phar",
+  "georgian_qar", "georgian_rae", "georgian_san", "georgian_shin",
+  "georgian_tan", "georgian_tar", "georgian_un", "georgian_vin", "georgian_we",
+  "georgian_xan", "georgian_zen", "georgian_zhar", "go", "graphicseditor",
+  "grave", "greater", "greaterthanequal", "greek_accentdieresis",
+  "greek_alpha", "greek_alphaaccent", "greek_beta", "greek_chi", "greek_delta",
+  "greek_epsilon", "greek_epsilonaccent", "greek_eta", "greek_etaaccent",
+  "greek_finalsmallsigma", "greek_gamma", "greek_horizbar", "greek_iota",
+  "greek_iotaaccent", "greek_iotaaccentdieresis", "greek_iotadiaeresis",
+  "greek_iotadieresis", "greek_kappa", "greek_lambda", "greek_lamda",
+  "greek_mu", "greek_nu", "greek_omega", "greek_omegaaccent", "greek_omicron",
+  "greek_omicronaccent", "greek_phi", "greek_pi", "greek_psi", "greek_rho",
+  "greek_sigma", "greek_switch", "greek_tau", "greek_theta", "greek_upsilon",
+  "greek_upsilonaccent", "greek_upsilonaccentdieresis",
+  "greek_upsilondieresis", "greek_xi", "greek_zeta", "green", "guillemotleft",
+  "guillemotright", "h", "hairspace", "hangul", "hangul_a", "hangul_ae",
+  "hangul_araea", "hangul_araeae", "hangul_banja", "hangul_cieuc",
+  "hangul_codeinput", "hangul_dikeud", "hangul_e", "hangul_end", "hangul_eo",
+  "hangul_eu", "hangul_hanja", "hangul_hieuh", "hangul_i", "hangul_ieung",
+  "hangul_j_cieuc", "hangul_j_dikeud", "hangul_j_hieuh", "hangul_j_ieung",
+  "hangul_j_jieuj", "hangul_j_khieuq", "hangul_j_kiyeog",
+  "hangul_j_kiyeogsios", "hangul_j_kkogjidalrinieung", "hangul_j_mieum",
+  "hangul_j_nieun", "hangul_j_nieunhieuh", "hangul_j_nieunjieuj",
+  "hangul_j_pansios", "hangul_j_phieuf", "hangul_j_pieub",
+  "hangul_j_pieubsios", "hangul_j_rieul", "hangul_j_rieulhieuh",
+  "hangul_j_rieulkiyeog", "hangul_j_rieulmieum", "hangul_j_rieulphieuf",
+  "hangul_j_rieulpieub", "hangul_j_rieulsios", "hangul_j_rieultieut",
+  "hangul_j_sios", "hangul_j_ssangkiyeog", "hangul_j_ssangsios",
+  "hangul_j_tieut", "hangul_j_yeorinhieuh", "hangul_jamo", "hangul_jeonja",
+  "hangul_jieuj", "hangul_khieuq", "hangul_kiyeog", "hangul_kiyeogsios",
+  "hangul_kkogjidalrinieung", "hangul_mieum", "hangul_multiplecandidate",
+  "hangul_nieun", "hangul_nieunhieuh", "hangul_nieunjieuj", "hangul_o",
+  "hangul_oe", "hangul_pansios", "hangul_phieuf", "hangul_pieub",
+  "hangul_pieubsios", "hangul_posthanja", "hangul_prehanja",
+  "hangul_previouscandidate", "hangul_rieul", "hangul_rieulhieuh",
+  "hangul_rieulkiyeog", "hangul_rieulmieum", "hangul_rieulphieuf",
+  "hangul_rieulpieub", "hangul_rieulsios", "hangul_rieultieut",
+  "hangul_rieulyeorinhieuh", "hangul_romaja", "hangul_singlecandidate",
+  "hangul_sios", "hangul_special", "hangul_ssangdikeud", "hangul_ssangjieuj",
+  "hangul_ssangkiyeog", "hangul_ssangpieub", "hangul_ssangsios",
+  "hangul_start", "hangul_sunkyeongeummieum", "hangul_sunkyeongeumphieuf",
+  "hangul_sunkyeongeumpieub", "hangul_switch", "hangul_tieut", "hangul_u",
+  "hangul_wa", "hangul_wae", "hangul_we", "hangul_weo", "hangul_wi",
+  "hangul_ya", "hangul_yae", "hangul_ye", "hangul_yeo", "hangul_yeorinhieuh",
+  "hangul_yi", "hangul_yo", "hangul_yu", "hangupphone", "hankaku",
+  "hcircumflex", "heart", "hebrew_aleph", "hebrew_ayin", "hebrew_bet",
+  "hebrew_beth", "hebrew_chet", "hebrew_dalet", "hebrew_daleth",
+  "hebrew_doublelowline", "hebrew_finalkaph", "hebrew_finalmem",
+  "hebrew_finalnun", "hebrew_finalpe", "hebrew_finalzade", "hebrew_finalzadi",
+  "hebrew_gimel", "hebrew_gimmel", "hebrew_he", "hebrew_het", "hebrew_kaph",
+  "hebrew_kuf", "hebrew_lamed", "hebrew_mem", "hebrew_nun", "hebrew_pe",
+  "hebrew_qoph", "hebrew_resh", "hebrew_samech", "hebrew_samekh",
+  "hebrew_shin", "hebrew_switch", "hebrew_taf", "hebrew_taw", "hebrew_tet",
+  "hebrew_teth", "hebrew_waw", "hebrew_yod", "hebrew_zade", "hebrew_zadi",
+  "hebrew_zain", "hebrew_zayin", "help", "henkan", "henkan_mode", "hexagram",
+  "hibernate", "hiragana", "hiragana_katakana", "history", "home", "homepage",
+  "horizconnector", "horizlinescan1", "horizlinescan3", "horizlinescan5",
+  "horizlinescan7", "horizlinescan9", "hotlinks", "hstroke", "ht", "hyper_l",
+  "hyper_r", "hyphen", "i", "iabovedot", "iacute", "ibelowdot", "ibreve",
+  "icircumflex", "identical", "idiaeresis", "idotless", "ifonlyif", "igrave",
+  "ihook", "imacron", "images", "implies", "includedin", "includes",
+  "infinity", "info", "insert", "integral", "intersection", "iogonek",
+  "iso_center_object", "iso_continuous_underline",
+  "iso_discontinuous_underline", "iso_emphasize", "iso_enter",
+  "iso_fast_cursor_down", "iso_fast_cursor_left", "iso_fast_cursor_right",
+  "iso_fast_cursor_up", "iso_first_group", "iso_first_group_lock",
+  "iso_group_latch", "iso_group_lock", "iso_group_shift", "iso_last_group",
+  "iso_last_group_lock", "iso_left_tab", "iso_level2_latch",
+  "iso_level3_latch", "iso_level3_lock", "iso_level3_shift",
+  "iso_level5_latch", "iso_level5_lock", "iso_level5_shift", "iso_lock",
+  "iso_move_line_down", "iso_move_line_up", "iso_next_group",
+  "iso_next_group_lock", "iso_partial_line_down", "iso_partial_line_up",
+  "iso_partial_space_left", "iso_partial_space_right", "iso_prev_group",
+  "iso_prev_group_lock", "iso_release_both_margins", "iso_release_margin_left",
+  "iso_release_margin_right", "iso_set_margin_left", "iso_set_margin_right",
+  "itilde", "itouch", "j", "jcircumflex", "jot", "journal", "k", "kana_a",
+  "kana_chi", "kana_closingbracket", "kana_comma", "kana_conjunctive",
+  "kana_e", "kana_fu", "kana_fullstop", "kana_ha", "kana_he", "kana_hi",
+  "kana_ho", "kana_hu", "kana_i", "kana_ka", "kana_ke", "kana_ki", "kana_ko",
+  "kana_ku", "kana_lock", "kana_ma", "kana_me", "kana_mi", "kana_middledot",
+  "kana_mo", "kana_mu", "kana_n", "kana_na", "kana_ne", "kana_ni", "kana_no",
+  "kana_nu", "kana_o", "kana_openingbracket", "kana_ra", "kana_re", "kana_ri",
+  "kana_ro", "kana_ru", "kana_sa", "kana_se", "kana_shi", "kana_shift",
+  "kana_so", "kana_su", "kana_switch", "kana_ta", "kana_te", "kana_ti",
+  "kana_to", "kana_tsu", "kana_tu", "kana_u", "kana_wa", "kana_wo", "kana_ya",
+  "kana_yo", "kana_yu", "kanji", "kanji_bangou", "kappa", "katakana",
+  "kbdbrightnessdown", "kbdbrightnessup", "kbdinputassistaccept",
+  "kbdinputassistcancel", "kbdinputassistnext", "kbdinputassistnextgroup",
+  "kbdinputassistprev", "kbdinputassistprevgroup", "kbdlcdmenu1",
+  "kbdlcdmenu2", "kbdlcdmenu3", "kbdlcdmenu4", "kbdlcdmenu5", "kbdlightonoff",
+  "kcedilla", "keyboard", "korean_won", "kp_0", "kp_1", "kp_2", "kp_3", "kp_4",
+  "kp_5", "kp_6", "kp_7", "kp_8", "kp_9", "kp_add", "kp_begin", "kp_decimal",
+  "kp_delete", "kp_divide", "kp_down", "kp_end", "kp_enter", "kp_equal",
+  "kp_f1", "kp_f2", "kp_f3", "kp_f4", "kp_home", "kp_insert", "kp_left",
+  "kp_multiply", "kp_next", "kp_page_down", "kp_page_up", "kp_prior",
+  "kp_right", "kp_separator", "kp_space", "kp_subtract", "kp_tab", "kp_up",
+  "kra", "l", "l1", "l10", "l2", "l3", "l4", "l5", "l6", "l7", "l8", "l9",
+  "lacute", "last_virtual_screen", "latincross", "launch0", "launch1",
+  "launch2", "launch3", "launch4", "launch5", "launch6", "launch7", "launch8",
+  "launch9", "launcha", "launchb", "launchc", "launchd", "launche", "launchf",
+  "lbelowdot", "lcaron", "lcedilla", "left", "leftanglebracket", "leftarrow",
+  "leftcaret", "leftdoublequotemark", "leftdown", "leftmiddlecurlybrace",
+  "leftopentriangle", "leftpointer", "leftradical", "leftshoe",
+  "leftsinglequotemark", "leftt", "lefttack", "leftup", "less",
+  "lessthanequal", "lf", "lightbulb", "lightstoggle", "linefeed", "lirasign",
+  "loggrabinfo", "logicaland", "logicalor", "logoff", "logwindowtree",
+  "lowleftcorner", "lowrightcorner", "lstroke", "m", "mabovedot",
+  "macedonia_dse", "macedonia_gje", "macedonia_kje", "macro1", "macro10",
+  "macro11", "macro12", "macro13", "macro14", "macro15", "macro16", "macro17",
+  "macro18", "macro19", "macro2", "macro20", "macro21", "macro22", "macro23",
+  "macro24", "macro25", "macro26", "macro27", "macro28", "macro29", "macro3",
+  "macro30", "macro4", "macro5", "macro6", "macro7", "macro8", "macro9",
+  "macron", "macropreset1", "macropreset2", "macropreset3", "macropresetcycle",
+  "macrorecordstart", "macrorecordstop", "mae_koho", "mail", "mailforward",
+  "malesymbol", "maltesecross", "marker", "market", "masculine", "massyo",
+  "mediarepeat", "mediatopmenu", "meeting", "memo", "menu", "menukb", "menupb",
+  "messenger", "meta_l", "meta_r", "millsign", "minus", "minutes",
+  "mode_switch", "modelock", "monbrightnesscycle", "monbrightnessdown",
+  "monbrightnessup", "mousekeys_accel_enable", "mousekeys_enable", "mu",
+  "muhenkan", "multi_key", "multiplecandidate", "multiply", "music",
+  "musicalflat", "musicalsharp", "mycomputer", "mysites", "n", "nabla",
+  "nacute", "nairasign", "ncaron", "ncedilla", "new", "news", "newsheqelsign",
+  "next", "next_virtual_screen", "next_vmode", "nextfavorite", "ninesubscript",
+  "ninesuperior", "nl", "nobreakspace", "notapproxeq", "notelementof",
+  "notequal", "notidentical", "notificationcenter", "notsign", "ntilde",
+  "num_lock", "numbersign", "numeric0", "numeric1", "numeric11", "numeric12",
+  "numeric2", "numeric3", "numeric4", "numeric5", "numeric6", "numeric7",
+  "numeric8", "numeric9", "numerica", "numericb", "numericc", "numericd",
+  "numericpound", "numericstar", "numerosign", "o", "oacute", "obarred",
+  "obelowdot", "ocaron", "ocircumflex", "ocircumflexacute",
+  "ocircumflexbelowdot", "ocircumflexgrave", "ocircumflexhook",
+  "ocircumflextilde", "odiaeresis", "odoubleacute", "oe", "officehome",
+  "ogonek", "ograve", "ohook", "ohorn", "ohornacute", "ohornbelowdot",
+  "ohorngrave", "ohornhook", "ohorntilde", "omacron", "oneeighth", "onefifth",
+  "onehalf", "onequarter", "onesixth", "onesubscript", "onesuperior",
+  "onethird", "onscreenkeyboard", "ooblique", "open", "openrectbullet",
+  "openstar", "opentribulletdown", "opentribulletup", "openurl", "option",
+  "ordfeminine", "oslash", "otilde", "overbar", "overlay1_enable",
+  "overlay2_enable", "overline", "p", "pabovedot", "page_down", "page_up",
+  "paragraph", "parenleft", "parenright", "partdifferential",
+  "partialderivative", "paste", "pause", "pauserecord", "percent", "period",
+  "periodcentered", "permille", "pesetasign", "phone", "phonographcopyright",
+  "pickupphone", "pictures", "plus", "plusminus", "pointer_accelerate",
+  "pointer_button1", "pointer_button2", "pointer_button3", "pointer_button4",
+  "pointer_button5", "pointer_button_dflt", "pointer_dblclick1",
+  "pointer_dblclick2", "pointer_dblclick3", "pointer_dblclick4",
+  "pointer_dblclick5", "pointer_dblclick_dflt", "pointer_dfltbtnnext",
+  "pointer_dfltbtnprev", "pointer_down", "pointer_downleft",
+  "pointer_downright", "pointer_drag1", "pointer_drag2", "pointer_drag3",
+  "pointer_drag4", "pointer_drag5", "pointer_drag_dflt", "pointer_enablekeys",
+  "pointer_left", "pointer_right", "pointer_up", "pointer_upleft",
+  "pointer_upright", "powerdown", "poweroff", "prescription", "presentation",
+  "prev_virtual_screen", "prev_vmode", "previouscandidate", "print", "prior",
+  "privacyscreentoggle", "prolongedsound", "punctspace", "q", "quad",
+  "question", "questiondown", "quotedbl", "quoteleft", "quoteright", "r", "r1",
+  "r10", "r11", "r12", "r13", "r14", "r15", "r2", "r3", "r4", "r5", "r6", "r7",
+  "r8", "r9", "racute", "radical", "rcaron", "rcedilla", "red", "redo",
+  "refresh", "registered", "reload", "repeatkeys_enable", "reply", "return",
+  "rfkill", "right", "rightanglebracket", "rightarrow", "rightcaret",
+  "rightdoublequotemark", "rightdown", "rightmiddlecurlybrace",
+  "rightmiddlesummation", "rightopentriangle", "rightpointer", "rightshoe",
+  "rightsinglequotemark", "rightt", "righttack", "rightup", "rockerdown",
+  "rockerenter", "rockerup", "romaji", "rootmenu", "rotatewindows",
+  "rotationkb", "rotationlocktoggle", "rotationpb", "rupeesign", "s",
+  "sabovedot", "sacute", "save", "scaron", "scedilla", "schwa", "scircumflex",
+  "screensaver", "script_switch", "scroll_lock", "scrollclick", "scrolldown",
+  "scrollup", "search", "seconds", "section", "select", "selectivescreenshot",
+  "semicolon", "semivoicedsound", "send", "serbian_dje", "serbian_dze",
+  "serbian_je", "serbian_lje", "serbian_nje", "serbian_tshe", "seveneighths",
+  "sevensubscript", "sevensuperior", "shift_l", "shift_lock", "shift_r",
+  "shop", "signaturemark", "signifblank", "similarequal", "singlecandidate",
+  "singlelowquotemark", "sinh_a", "sinh_aa", "sinh_aa2", "sinh_ae", "sinh_ae2",
+  "sinh_aee", "sinh_aee2", "sinh_ai", "sinh_ai2", "sinh_al", "sinh_au",
+  "sinh_au2", "sinh_ba", "sinh_bha", "sinh_ca", "sinh_cha", "sinh_dda",
+  "sinh_ddha", "sinh_dha", "sinh_dhha", "sinh_e", "sinh_e2", "sinh_ee",
+  "sinh_ee2", "sinh_fa", "sinh_ga", "sinh_gha", "sinh_h2", "sinh_ha", "sinh_i",
+  "sinh_i2", "sinh_ii", "sinh_ii2", "sinh_ja", "sinh_jha", "sinh_jnya",
+  "sinh_ka", "sinh_kha", "sinh_kunddaliya", "sinh_la", "sinh_lla", "sinh_lu",
+  "sinh_lu2", "sinh_luu", "sinh_luu2", "sinh_ma", "sinh_mba", "sinh_na",
+  "sinh_ndda", "sinh_ndha", "sinh_ng", "sinh_ng2", "sinh_nga", "sinh_nja",
+  "sinh_nna", "sinh_nya", "sinh_o", "sinh_o2", "sinh_oo", "sinh_oo2",
+  "sinh_pa", "sinh_pha", "sinh_ra", "sinh_ri", "sinh_rii", "sinh_ru2",
+  "sinh_ruu2", "sinh_sa", "sinh_sha", "sinh_ssha", "sinh_tha", "sinh_thha",
+  "sinh_tta", "sinh_ttha", "sinh_u", "sinh_u2", "sinh_uu", "sinh_uu2",
+  "sinh_va", "sinh_ya", "sixsubscript", "sixsuperior", "slash", "sleep",
+  "slowkeys_enable", "slowreverse", "soliddiamond", "space", "spell",
+  "spellcheck", "splitscreen", "squareroot", "ssharp", "standby", "start",
+  "sterling", "stickykeys_enable", "stop", "stoprecord", "stricteq",
+  "subtitle", "super_l", "super_r", "support", "suspend", "switch_vt_1",
+  "switch_vt_10", "switch_vt_11", "switch_vt_12", "switch_vt_2", "switch_vt_3",
+  "switch_vt_4", "switch_vt_5", "switch_vt_6", "switch_vt_7", "switch_vt_8",
+  "switch_vt_9", "sys_req", "t", "tab", "tabovedot", "taskmanager", "taskpane",
+  "tcaron", "tcedilla", "telephone", "telephonerecorder", "terminal",
+  "terminate_server", "thai_baht", "thai_bobaimai", "thai_chochan",
+  "thai_chochang", "thai_choching", "thai_chochoe", "thai_dochada",
+  "thai_dodek", "thai_fofa", "thai_fofan", "thai_hohip", "thai_honokhuk",
+  "thai_khokhai", "thai_khokhon", "thai_khokhuat", "thai_khokhwai",
+  "thai_khorakhang", "thai_kokai", "thai_lakkhangyao", "thai_lekchet",
+  "thai_lekha", "thai_lekhok", "thai_lekkao", "thai_leknung", "thai_lekpaet",
+  "thai_leksam", "thai_leksi", "thai_leksong", "thai_leksun", "thai_lochula",
+  "thai_loling", "thai_lu", "thai_maichattawa", "thai_maiek",
+  "thai_maihanakat", "thai_maihanakat_maitho", "thai_maitaikhu", "thai_maitho",
+  "thai_maitri", "thai_maiyamok", "thai_moma", "thai_ngongu", "thai_nikhahit",
+  "thai_nonen", "thai_nonu", "thai_oang", "thai_paiyannoi", "thai_phinthu",
+  "thai_phophan", "thai_phophung", "thai_phosamphao", "thai_popla",
+  "thai_rorua", "thai_ru", "thai_saraa", "thai_saraaa", "thai_saraae",
+  "thai_saraaimaimalai", "thai_saraaimaimuan", "thai_saraam", "thai_sarae",
+  "thai_sarai", "thai_saraii", "thai_sarao", "thai_sarau", "thai_saraue",
+  "thai_sarauee", "thai_sarauu", "thai_sorusi", "thai_sosala", "thai_soso",
+  "thai_sosua", "thai_thanthakhat", "thai_thonangmontho", "thai_thophuthao",
+  "thai_thothahan", "thai_thothan", "thai_thothong", "thai_thothung",
+  "thai_topatak", "thai_totao", "thai_wowaen", "thai_yoyak", "thai_yoying",
+  "therefore", "thinspace", "thorn", "threeeighths", "threefifths",
+  "threequarters", "threesubscript", "threesuperior", "time", "tintegral",
+  "todolist", "tools", "topintegral", "topleftparens", "topleftradical",
+  "topleftsqbracket", "topleftsummation", "topmenu", "toprightparens",
+  "toprightsqbracket", "toprightsummation", "topt",
+  "topvertsummationconnector", "touchpadoff", "touchpadon", "touchpadtoggle",
+  "touroku", "trademark", "trademarkincircle", "travel", "tslash", "twofifths",
+  "twosubscript", "twosuperior", "twothirds", "u", "uacute", "ubelowdot",
+  "ubreve", "ucircumflex", "udiaeresis", "udoubleacute", "ugrave", "uhook",
+  "uhorn", "uhornacute", "uhornbelowdot", "uhorngrave", "uhornhook",
+  "uhorntilde", "ukrainian_ghe_with_upturn", "ukrainian_i", "ukrainian_ie",
+  "ukrainian_yi", "ukranian_i", "ukranian_je", "ukranian_yi", "umacron",
+  "underbar", "underscore", "undo", "ungrab", "union", "unmute", "uogonek",
+  "up", "uparrow", "upcaret", "upleftcorner", "uprightcorner", "upshoe",
+  "upstile", "uptack", "uring", "user1kb", "user2kb", "userpb", "utilde",
+  "uwb", "v", "variation", "vendorhome", "vertbar", "vertconnector", "video",
+  "videophone", "view", "vod", "voicecommand", "voicedsound", "voicemail",
+  "voidsymbol", "vt", "w", "wacute", "wakeup", "wcircumflex", "wdiaeresis",
+  "webcam", "wgrave", "wheelbutton", "wlan", "wonsign", "word", "wpsbutton",
+  "wwan", "www", "x", "xabovedot", "xfer", "y", "yacute", "ybelowdot",
+  "ycircumflex", "ydiaeresis", "yellow", "yen", "ygrave", "yhook", "ytilde",
+  "z", "zabovedot", "zacute", "zcaron", "zen_koho", "zenkaku",
+  "zenkaku_hankaku", "zerosubscript", "zerosuperior", "zoomin", "zoomout",
+  "zoomreset", "zstroke",
+];
@@ -5,14 +5,76 @@ mod rtf;
 mod translation_model;
 mod translation_parse;
 mod translation;
+mod error;
+mod key_combo;
+mod key_names;
+mod dialect;
+mod roundtrip;
+mod translator;
+mod trie;
+mod search;
+mod history;
+mod order;
+mod stroke;
+mod system;
+mod stack;
+mod diff;
+mod merge;
+mod cache;
+mod mapped;
+mod shared;
+mod stats;
+mod conflicts;
+#[cfg(feature = "serde")]
+mod serialize;
 mod python;
 
+#[cfg(test)]
+mod test_cache;
+#[cfg(test)]
+mod test_conflicts;
+#[cfg(test)]
+mod test_dialect;
+#[cfg(test)]
+mod test_dict;
+#[cfg(test)]
+mod test_history;
+#[cfg(test)]
+mod test_diff;
+#[cfg(test)]
+mod test_key_combo;
+#[cfg(test)]
+mod test_mapped;
+#[cfg(test)]
+mod test_merge;
+#[cfg(test)]
+mod test_order;
+#[cfg(test)]
+mod test_roundtrip;
 #[cfg(test)]
 mod test_rtf;
 #[cfg(test)]
+mod test_search;
+#[cfg(all(test, feature = "serde"))]
+mod test_serialize;
+#[cfg(test)]
+mod test_shared;
+#[cfg(test)]
+mod test_stack;
+#[cfg(test)]
+mod test_stats;
+#[cfg(test)]
+mod test_stroke;
+#[cfg(test)]
+mod test_system;
+#[cfg(test)]
 mod test_translation;
 #[cfg(test)]
+mod test_translator;
+#[cfg(test)]
 mod test_translation_parse;
+#[cfg(test)]
+mod test_trie;
 
 #[allow(unused_imports)]
 #[macro_use]
@@ -20,7 +82,31 @@ extern crate lazy_static;
 extern crate nom;
 extern crate regex;
 
-pub use dict::Dictionary;
-pub use translation::format_plover_to_rtf;
+pub use dict::{Dictionary, Entry, EntryMetadata, MergedOutline};
+pub use error::Error;
+pub use key_combo::{KeyCombo, KeyPress, validate_key_combos};
+pub use translation::{format_plover_to_rtf, normalize_translation};
+pub use dialect::{
+  Dialect, DialectWarning, ImportProfile,
+  format_plover_to_rtf_dialect, format_rtf_to_plover_with_profile};
 pub use translation_parse::format_rtf_to_plover;
-pub use rtf::parse_rtf;
+pub use rtf::{
+  EntryReader, ParseOptions, ParsedDictionary, parse_rtf, parse_rtf_entries, parse_rtf_with_options,
+  parse_file_with_options, parse_file_with_profile, UnmappedControl};
+pub use translation_model::{Case, Object, ParagraphMode};
+pub use roundtrip::{
+  check_round_trip, check_rtf_round_trip, check_rtf_translation, check_translation, LossyEntry};
+pub use translator::{DEFAULT_UNDO_LEVELS, Event, Translation, Translator};
+pub use trie::StrokeTrie;
+pub use search::{Pattern, PatternKind, SearchField};
+pub use diff::{Change, Conflict, apply_patch, diff, parse_patch, write_patch};
+pub use merge::{Merge, MergeConflict, apply_resolutions, merge, write_resolutions};
+pub use order::{Order, WriteOptions, steno_sort_key};
+pub use cache::{CACHE_VERSION, parse_rtf_cached, parse_rtf_cached_with_options, read_cache, write_cache};
+pub use mapped::{MappedDictionary, write_index};
+pub use shared::SharedDictionary;
+pub use stats::{EntryKind, Stats, stats};
+pub use conflicts::{BoundaryConflict, boundary_conflicts};
+pub use stroke::{Stroke, format_outline, parse_outline};
+pub use system::{NumberKey, StenoSystem};
+pub use stack::{DictionaryStack, ShadowedEntry, StackEntry};
@@ -5,6 +5,22 @@ mod rtf;
 mod translation_model;
 mod translation_parse;
 mod translation;
+mod error;
+mod key_combo;
+mod key_names;
+mod dialect;
+mod roundtrip;
+mod translator;
+mod trie;
+mod search;
+mod history;
+mod order;
+mod stroke;
+mod system;
+mod stats;
+mod conflicts;
+mod diff;
+mod merge;
 
 #[macro_use]
 extern crate lazy_static;
@@ -19,12 +35,23 @@ use std::process::exit;
 use std::str::{FromStr, from_utf8};
 
 use encoding_rs::WINDOWS_1252;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use structopt::StructOpt;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
-use crate::dict::{Dictionary, Entry};
-use crate::rtf::parse_file;
+use crate::conflicts::boundary_conflicts;
+use crate::dialect::{Dialect, DialectWarning, ImportProfile};
+use crate::diff::{Change, Conflict, apply_patch, diff, parse_patch, write_patch};
+use crate::dict::{Dictionary, Entry, MergedOutline};
+use crate::error::Error;
+use crate::merge::{apply_resolutions, merge, write_resolutions};
+use crate::order::{Order, WriteOptions};
+use crate::rtf::{EntryReader, ParseOptions, ParsedDictionary, UnmappedControl, parse_file_with_options};
+use crate::roundtrip::{check_round_trip, check_rtf_round_trip};
+use crate::search::{Pattern, PatternKind, SearchField};
+use crate::stats::stats;
+use crate::system::{NumberKey, StenoSystem};
+use crate::translator::{Event, Translator};
 
 lazy_static!{
   static ref VERSION_STRING: String =
@@ -43,6 +70,16 @@ impl fmt::Display for CommandParseError {
 enum CommandMode {
   Convert,
   CountEntries,
+  Stats,
+  CheckRoundTrip,
+  Validate,
+  Conflicts,
+  Translate,
+  Search,
+  Diff,
+  Patch,
+  Merge,
+  Resolve,
 }
 impl FromStr for CommandMode {
   type Err = CommandParseError;
@@ -51,27 +88,128 @@ impl FromStr for CommandMode {
     match s {
       "convert" => Ok(Self::Convert),
       "count" => Ok(Self::CountEntries),
+      "stats" => Ok(Self::Stats),
+      "check" => Ok(Self::CheckRoundTrip),
+      "validate" => Ok(Self::Validate),
+      "conflicts" => Ok(Self::Conflicts),
+      "translate" => Ok(Self::Translate),
+      "search" => Ok(Self::Search),
+      "diff" => Ok(Self::Diff),
+      "patch" => Ok(Self::Patch),
+      "merge" => Ok(Self::Merge),
+      "resolve" => Ok(Self::Resolve),
       _ => Err(CommandParseError {}),
     }
   }
 }
 
+#[derive(Debug)]
+struct JsonFormatParseError {}
+impl fmt::Display for JsonFormatParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "unknown JSON format")
+  }
+}
+/// How to write JSON dictionaries. Plover's own format only has outlines and
+/// translations; the full format keeps everything in the dictionary.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum JsonFormat {
+  Plover,
+  Full,
+}
+impl FromStr for JsonFormat {
+  type Err = JsonFormatParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "plover" => Ok(Self::Plover),
+      "full" => Ok(Self::Full),
+      _ => Err(JsonFormatParseError {}),
+    }
+  }
+}
+
 #[derive(StructOpt, Debug)]
 struct CommandLine {
   #[structopt(short, default_value = "convert")]
-  /// The mode this program should run in, one of 'convert' or 'count'.
+  /// The mode this program should run in, one of 'convert', 'count',
+  /// 'stats', 'check', 'validate', 'conflicts', 'translate', 'search',
+  /// 'diff', 'patch', 'merge' or 'resolve'.
   mode: CommandMode,
   #[structopt(parse(from_os_str))]
   /// The path of the file to convert. Must have a .rtf or .json extension.
   input: PathBuf,
-  #[structopt(parse(from_os_str), required_if("mode", "convert"))]
-  /// The path of the output file. Must have a .rtf or .json extension.
+  #[structopt(parse(from_os_str), required_ifs(&[
+    ("mode", "convert"), ("mode", "diff"), ("mode", "patch"), ("mode", "merge"),
+    ("mode", "resolve")]))]
+  /// The path of the output file. Must have a .rtf or .json extension. In
+  /// 'diff' mode, the dictionary to compare the input against.
   output: Option<PathBuf>,
-}
-
-enum Direction {
-  RtfToJson,
-  JsonToRtf,
+  #[structopt(long, default_value = "plover")]
+  /// The dialect of RTF to read or write, one of 'plover', 'catalyst',
+  /// 'eclipse', 'procat' or 'stenocat'.
+  dialect: Dialect,
+  #[structopt(long, default_value = "english")]
+  /// The steno system outlines are written in, used to sort, validate and
+  /// translate them: 'english', 'palantype', or the path of a JSON file
+  /// defining the system.
+  system: String,
+  #[structopt(long)]
+  /// Rewrite outlines in canonical form for the steno system when reading
+  /// dictionaries, merging entries whose outlines become the same.
+  normalize: bool,
+  #[structopt(long, default_value = "keep")]
+  /// How to rewrite strokes that use the number key when reading
+  /// dictionaries: 'keep' them as written, write them with 'digits' in place
+  /// of the number key as Plover does (1-9), write them 'explicit'ly with the
+  /// number key and letters (#S-T), or 'strip' the number key from outlines
+  /// that also have digits (#1-9 as 1-9). Entries merged by the rewrite are
+  /// reported.
+  read_number_key: NumberKey,
+  #[structopt(long, default_value = "keep")]
+  /// How to rewrite strokes that use the number key when writing
+  /// dictionaries, one of 'keep', 'digits', 'explicit' or 'strip'.
+  write_number_key: NumberKey,
+  #[structopt(long, default_value = "plover")]
+  /// The format to write JSON dictionaries in: 'plover', which Plover can
+  /// read but only has outlines and translations, or 'full', which also keeps
+  /// comments and the system name. Both formats are read automatically.
+  json_format: JsonFormat,
+  #[structopt(long, default_value = "original")]
+  /// The order to write entries in, one of 'original', 'steno',
+  /// 'translation' or 'strokes'.
+  order: Order,
+  #[structopt(long, required_if("mode", "search"))]
+  /// The pattern to search for.
+  pattern: Option<String>,
+  #[structopt(long, default_value = "translation")]
+  /// The part of each entry to search, one of 'outline', 'translation' or
+  /// 'comment'.
+  field: SearchField,
+  #[structopt(long, default_value = "substring")]
+  /// How to match the pattern, one of 'substring', 'glob' or 'regex'.
+  kind: PatternKind,
+  #[structopt(long, default_value = "10")]
+  /// In 'stats' mode, the number of translations with the most outlines to
+  /// list.
+  top: usize,
+  #[structopt(long, parse(from_os_str), required_if("mode", "patch"))]
+  /// The patch file to write in 'diff' mode, or to apply in 'patch' mode.
+  patch: Option<PathBuf>,
+  #[structopt(long, parse(from_os_str), required_if("mode", "merge"))]
+  /// In 'merge' mode, the common ancestor of the input and --theirs.
+  base: Option<PathBuf>,
+  #[structopt(long, parse(from_os_str), required_if("mode", "merge"))]
+  /// In 'merge' mode, the dictionary whose changes are merged into the input.
+  theirs: Option<PathBuf>,
+  #[structopt(long, parse(from_os_str), required_if("mode", "resolve"))]
+  /// The resolution file to write in 'merge' mode, or to apply in 'resolve'
+  /// mode.
+  conflicts: Option<PathBuf>,
+  #[structopt(long)]
+  /// In 'merge' mode, write conflicting entries to the RTF output as \cxconf
+  /// groups offering both translations.
+  conflict_markers: bool,
 }
 
 lazy_static! {
@@ -80,6 +218,11 @@ lazy_static! {
     color.set_fg(Some(Color::Red)).set_bold(true);
     color
   };
+  static ref YELLOW: ColorSpec = {
+    let mut color = ColorSpec::new();
+    color.set_fg(Some(Color::Yellow)).set_bold(true);
+    color
+  };
 }
 
 fn error(out: &mut StandardStream, message: String) -> Result<(), io::Error> {
@@ -91,6 +234,15 @@ fn error(out: &mut StandardStream, message: String) -> Result<(), io::Error> {
   Ok(())
 }
 
+fn warning(out: &mut StandardStream, message: String) -> Result<(), io::Error> {
+  out.set_color(&YELLOW)?;
+  write!(out, "warning: ")?;
+  out.reset()?;
+  writeln!(out, "{}", message)?;
+
+  Ok(())
+}
+
 enum RtfCreError {
   InvalidArgument,
   IoError { err: io::Error },
@@ -98,6 +250,14 @@ enum RtfCreError {
   RtfWriteError,
   JsonParseError,
   JsonWriteError,
+  LossyEntries(usize),
+  InvalidEntries(usize),
+  InvalidPattern { err: Error },
+  InvalidStenoSystem { err: Error },
+  InvalidPatch { err: Error },
+  PatchConflicts(usize),
+  InvalidResolution { err: Error },
+  MergeConflicts(usize),
 }
 impl From<io::Error> for RtfCreError {
   fn from(err: io::Error) -> Self { Self::IoError { err } }
@@ -106,6 +266,14 @@ impl fmt::Display for RtfCreError {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self {
       Self::IoError { err } => write!(f, "I/O error: {:?}", err),
+      Self::LossyEntries(n) =>
+        write!(f, "{} entries do not survive a round trip", n),
+      Self::InvalidEntries(n) => write!(f, "{} entries are not valid", n),
+      Self::InvalidPattern { err } | Self::InvalidStenoSystem { err } |
+        Self::InvalidPatch { err } | Self::InvalidResolution { err } => write!(f, "{}", err),
+      Self::PatchConflicts(n) =>
+        write!(f, "{} changes conflict with the input; nothing was written", n),
+      Self::MergeConflicts(n) => write!(f, "{} entries have unresolved conflicts", n),
       _ => write!(f, "{}", match self {
         Self::InvalidArgument => "invalid arguments",
         Self::RtfParseError => "could not parse RTF file",
@@ -118,8 +286,235 @@ impl fmt::Display for RtfCreError {
   }
 }
 
+fn read_dictionary(path: &PathBuf, dialect: Dialect, options: &ParseOptions)
+    -> Result<Dictionary, RtfCreError> {
+  let mut input = File::open(path)?;
+  let mut buf = Vec::new();
+  input.read_to_end(&mut buf)?;
+
+  let (dict, merged) = match path.extension().and_then(|x| x.to_str()) {
+    Some("rtf") => {
+      let (decoded, _, _) = WINDOWS_1252.decode(&buf[..]);
+      let profile = match dialect {
+        Dialect::Plover => None,
+        _ => Some(ImportProfile::for_dialect(dialect)),
+      };
+      match parse_file_with_options(&decoded, options, profile.as_ref()) {
+        Err(e) => {
+          eprintln!("{:?}", e);
+          Err(RtfCreError::RtfParseError)
+        },
+        Ok((_, ParsedDictionary { dict, unmapped, merged })) => {
+          let mut stderr = StandardStream::stderr(ColorChoice::Always);
+          for UnmappedControl { steno, control } in unmapped {
+            warning(&mut stderr, format!("{}: could not map \\{}", steno, control))?;
+          }
+          Ok((dict, merged))
+        },
+      }
+    },
+    Some("json") => {
+      let contents = match from_utf8(buf.as_slice()) {
+        Ok(contents) => Ok(contents),
+        Err(_) => Err(RtfCreError::JsonParseError),
+      }?;
+      match serde_json::from_str(contents) {
+        // A dictionary written in the full format, with its comments and
+        // system name
+        Ok(Value::Object(map)) if map.get("entries").map_or(false, Value::is_array) => {
+          let options = full_json_options(&map, options)?;
+          Ok(read_full_json(&map, &options)?.finish())
+        },
+        Ok(Value::Object(map)) => {
+          let mut reader = EntryReader::new(options, Dictionary::new(&VERSION_STRING));
+          for (steno, value) in map.iter() {
+            if let Value::String(translation) = value {
+              reader.add_entry(String::from(steno), translation.clone(), None);
+            }
+          }
+          Ok(reader.finish())
+        },
+        _ => Err(RtfCreError::JsonParseError),
+      }
+    },
+    _ => Err(RtfCreError::InvalidArgument),
+  }?;
+
+  let mut stderr = StandardStream::stderr(ColorChoice::Always);
+  for MergedOutline { steno, replaced, kept } in merged {
+    warning(&mut stderr, format!("{}: {} ({:?}) was replaced by {} ({:?})",
+      steno, replaced.steno, replaced.translation, kept.steno, kept.translation))?;
+  }
+  Ok(dict)
+}
+
+// The options to read a dictionary in the full JSON format with, taking the
+// steno system from the file and normalizing outlines if it was normalized
+fn full_json_options(map: &Map<String, Value>, options: &ParseOptions)
+    -> Result<ParseOptions, RtfCreError> {
+  let steno_system = match map.get("steno_system") {
+    Some(Value::String(system)) => StenoSystem::find(system)
+      .map_err(|err| RtfCreError::InvalidStenoSystem { err })?,
+    Some(_) => return Err(RtfCreError::JsonParseError),
+    None => options.steno_system.clone(),
+  };
+  let normalize = map.get("normalize").and_then(Value::as_bool).unwrap_or(false);
+  Ok(ParseOptions {
+    steno_system,
+    normalize_outlines: options.normalize_outlines || normalize,
+    ..options.clone()
+  })
+}
+
+fn read_full_json<'a>(map: &Map<String, Value>, options: &'a ParseOptions)
+    -> Result<EntryReader<'a>, RtfCreError> {
+  let cre_system = map.get("cre_system").and_then(Value::as_str)
+    .ok_or(RtfCreError::JsonParseError)?;
+  let mut reader = EntryReader::new(options, Dictionary::new(cre_system));
+  for entry in map["entries"].as_array().into_iter().flatten() {
+    let (steno, translation) = match (entry.get("steno"), entry.get("translation")) {
+      (Some(Value::String(steno)), Some(Value::String(translation))) => (steno, translation),
+      _ => return Err(RtfCreError::JsonParseError),
+    };
+    let comment = entry.get("metadata")
+      .and_then(|metadata| metadata.get("comment"))
+      .and_then(Value::as_str)
+      .map(String::from);
+    reader.add_entry(steno.clone(), translation.clone(), comment);
+  }
+  Ok(reader)
+}
+
+// The dictionary in the full JSON format, the same as it is serialized with
+// the serde feature, with outlines rewritten for `options`. Rewritten
+// outlines that become the same keep the later entry.
+fn full_json(dict: &Dictionary, options: &WriteOptions) -> Value {
+  let mut ordered = Dictionary::new(&dict.cre_system);
+  for entry in dict.entries_in_order(options.order) {
+    let steno = dict.steno_system.write_numbers(&entry.steno, options.number_key);
+    ordered.add_entry(steno.into_owned(), entry.translation.to_string(), entry.comment());
+  }
+
+  let entries = ordered.entries.values().map(|entry| {
+    let mut map = Map::new();
+    map.insert("steno".to_string(), Value::from(entry.steno()));
+    map.insert("translation".to_string(), Value::from(entry.translation()));
+    if let Some(comment) = entry.comment() {
+      let mut metadata = Map::new();
+      metadata.insert("comment".to_string(), Value::String(comment));
+      map.insert("metadata".to_string(), Value::Object(metadata));
+    }
+    Value::Object(map)
+  }).collect();
+
+  let mut map = Map::new();
+  map.insert("cre_system".to_string(), Value::from(dict.cre_system.as_str()));
+  map.insert("steno_system".to_string(), Value::from(dict.steno_system.id()));
+  map.insert("normalize".to_string(), Value::Bool(dict.normalizes_outlines()));
+  map.insert("entries".to_string(), Value::Array(entries));
+  Value::Object(map)
+}
+
+fn write_dictionary(dict: Dictionary, path: &PathBuf, options: &WriteOptions,
+    json_format: JsonFormat) -> Result<Vec<(String, DialectWarning)>, RtfCreError> {
+  match path.extension().and_then(|x| x.to_str()) {
+    Some("rtf") => {
+      let mut output = File::create(path)?;
+      let mut buf = Vec::new();
+      let warnings = dict.write_with_options(&mut buf, options)?;
+      let out = std::str::from_utf8(buf.as_slice()).unwrap();
+      let (encoded, _, _) = WINDOWS_1252.encode(out);
+      output.write_all(&encoded)?;
+
+      Ok(warnings)
+    },
+    Some("json") => {
+      let mut output = File::create(path)?;
+      let written = match json_format {
+        JsonFormat::Plover => {
+          let mut map = serde_json::Map::with_capacity(dict.len());
+          for Entry { steno, translation, .. } in dict.entries_in_order(options.order) {
+            let steno = dict.steno_system.write_numbers(steno, options.number_key);
+            map.insert(steno.to_string(), Value::String(translation.to_string()));
+          }
+          serde_json::to_writer_pretty(&mut output, &map)
+        },
+        JsonFormat::Full => serde_json::to_writer_pretty(&mut output, &full_json(&dict, options)),
+      };
+      match written {
+        Ok(_) => Ok(vec![]),
+        Err(_) => Err(RtfCreError::JsonWriteError),
+      }
+    },
+    _ => Err(RtfCreError::InvalidArgument),
+  }
+}
+
+fn report_dialect_warnings(warnings: Vec<(String, DialectWarning)>) -> Result<(), io::Error> {
+  let mut stderr = StandardStream::stderr(ColorChoice::Always);
+  for (steno, DialectWarning { object, message, dropped }) in warnings {
+    warning(&mut stderr, format!("{}: {} {}: {}", steno,
+      if dropped { "dropped" } else { "approximated" }, object, message))?;
+  }
+  Ok(())
+}
+
+fn describe_comment(comment: &Option<String>) -> String {
+  match comment {
+    Some(comment) => format!("{:?}", comment),
+    None => "(none)".to_string(),
+  }
+}
+
+fn describe_change(change: &Change) -> String {
+  match change {
+    Change::Added { steno, translation, .. } =>
+      format!("+ {}: {:?}", steno, translation),
+    Change::Removed { steno, translation, .. } =>
+      format!("- {}: {:?}", steno, translation),
+    Change::Retranslated { steno, old, new } =>
+      format!("~ {}: {:?} -> {:?}", steno, old, new),
+    Change::Recommented { steno, old, new } =>
+      format!("# {}: comment {} -> {}", steno, describe_comment(old), describe_comment(new)),
+  }
+}
+
+fn describe_entry(entry: &Option<Entry>) -> String {
+  match entry {
+    Some(entry) => format!("{:?}", entry.translation),
+    None => "(deleted)".to_string(),
+  }
+}
+
+// Accepts either plain strokes separated by whitespace, or lines from
+// Plover's stroke log such as `... Stroke(KAT : ['K-', 'A-', '-T'])`.
+fn strokes_from_line(line: &str) -> Vec<String> {
+  match line.find("Stroke(") {
+    Some(i) => line[i + "Stroke(".len()..]
+      .split(|c: char| c.is_whitespace() || c == ')')
+      .next()
+      .map(|stroke| vec![stroke.to_string()])
+      .unwrap_or_default(),
+    None => line.split_whitespace().map(String::from).collect(),
+  }
+}
+
 fn run_main() -> Result<(), RtfCreError> {
   let args = CommandLine::from_args();
+  let write_options = WriteOptions {
+    order: args.order,
+    dialect: args.dialect,
+    number_key: args.write_number_key,
+  };
+  let steno_system = match StenoSystem::find(&args.system) {
+    Ok(system) => Ok(system),
+    Err(err) => Err(RtfCreError::InvalidStenoSystem { err }),
+  }?;
+  let parse_options = ParseOptions {
+    steno_system,
+    number_key: args.read_number_key,
+    normalize_outlines: args.normalize,
+  };
 
   match args.mode {
     CommandMode::Convert => {
@@ -127,64 +522,16 @@ fn run_main() -> Result<(), RtfCreError> {
       let extensions = (
         match args.input.extension() { Some(x) => x.to_str(), None => None },
         match output.extension() { Some(x) => x.to_str(), None => None });
-      let direction = match extensions {
-        (Some("rtf"), Some("json")) => Ok(Direction::RtfToJson),
-        (Some("json"), Some("rtf")) => Ok(Direction::JsonToRtf),
-        (Some(x), Some(y)) if x == y => Err(RtfCreError::InvalidArgument),
+      match extensions {
+        (Some("rtf"), Some("json")) | (Some("json"), Some("rtf")) => Ok(()),
         _ => Err(RtfCreError::InvalidArgument),
       }?;
 
-      let mut input = File::open(args.input)?;
-      let mut output = File::create(output)?;
-      let mut buf = Vec::new();
-      input.read_to_end(&mut buf)?;
-      let contents = match direction {
-        Direction::RtfToJson => {
-          let (decoded, _, _) = WINDOWS_1252.decode(&buf[..]);
-          String::from(decoded)
-        },
-        Direction::JsonToRtf => from_utf8(buf.as_slice()).unwrap().to_string()
-      };
-      match direction {
-        Direction::RtfToJson => {
-          let dict = match parse_file(&contents) {
-            Err(e) => {
-              eprintln!("{:?}", e);
-              Err(RtfCreError::RtfParseError)
-            },
-            Ok((_, d)) => Ok(d),
-          }?;
-          let mut map = serde_json::Map::with_capacity(dict.len());
-          for (steno, Entry { translation, .. }) in dict.entries {
-            map.insert(steno, Value::String(translation));
-          }
-          match serde_json::to_writer_pretty(&mut output, &map) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(RtfCreError::JsonWriteError),
-          }
-        },
-        Direction::JsonToRtf => {
-          let mut dict = Dictionary::new(&VERSION_STRING);
-          match serde_json::from_str(&contents) {
-            Ok(Value::Object(map)) => {
-              for (steno, value) in map.iter() {
-                if let Value::String(translation) = value {
-                  dict.add_entry(String::from(steno), translation.clone(), None);
-                }
-              }
-
-              let mut buf = Vec::new();
-              dict.write(&mut buf)?;
-              let out = std::str::from_utf8(buf.as_slice()).unwrap();
-              let (encoded, _, _) = WINDOWS_1252.encode(out);
-              output.write(&encoded)?;
-
-              Ok(())
-            },
-            _ => Err(RtfCreError::JsonParseError),
-          }
-        },
-      }
+      let dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      let warnings = write_dictionary(dict, &output, &write_options, args.json_format)?;
+      report_dialect_warnings(warnings)?;
+
+      Ok(())
     },
     CommandMode::CountEntries => {
       let _ext = match args.input.extension() {
@@ -195,21 +542,223 @@ fn run_main() -> Result<(), RtfCreError> {
         _ => Err(RtfCreError::InvalidArgument),
       }?;
 
-      let mut input = File::open(args.input)?;
-      let mut contents = String::new();
-      input.read_to_string(&mut contents)?;
-      let dict = match parse_file(&contents) {
-        Err(e) => {
-          eprintln!("{:?}", e);
-          Err(RtfCreError::RtfParseError)
+      let dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      println!("{:?}", dict.len());
+
+      Ok(())
+    },
+    CommandMode::Stats => {
+      let dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      let stats = stats(&dict, args.top);
+      println!("Entries: {}", stats.entries);
+      println!("Longest key: {}", stats.longest_key);
+      println!("Strokes:");
+      for (strokes, count) in &stats.stroke_counts {
+        println!("  {}: {}", strokes, count);
+      }
+      println!("Kinds:");
+      for (kind, count) in &stats.kinds {
+        println!("  {}: {}", kind, count);
+      }
+      println!("Comments: {} ({:.1}%)", stats.commented, stats.comment_coverage() * 100.0);
+      if !stats.most_outlines.is_empty() {
+        println!("Most outlines:");
+        for (translation, count) in &stats.most_outlines {
+          println!("  {:?}: {}", translation, count);
+        }
+      }
+
+      Ok(())
+    },
+    CommandMode::CheckRoundTrip => {
+      // Check RTF dictionaries from the RTF as written, so that anything
+      // lost on import is reported too
+      let lossy = match (args.input.extension().and_then(|x| x.to_str()), args.dialect) {
+        (Some("rtf"), Dialect::Plover) => {
+          let mut buf = Vec::new();
+          File::open(&args.input)?.read_to_end(&mut buf)?;
+          let (decoded, _, _) = WINDOWS_1252.decode(&buf[..]);
+          check_rtf_round_trip(&decoded).ok_or(RtfCreError::RtfParseError)?
         },
-        Ok((_, d)) => Ok(d),
+        _ => check_round_trip(&read_dictionary(&args.input, args.dialect, &parse_options)?),
+      };
+      for entry in &lossy {
+        println!("{}: {:?}", entry.steno, entry.translation);
+        println!("  RTF:    {:?}", entry.rtf);
+        if let Some(plover) = &entry.plover_round_trip {
+          println!("  Plover: {:?} -> {:?}", entry.translation, plover);
+        }
+        if let Some(rtf) = &entry.rtf_round_trip {
+          println!("  RTF:    {:?} -> {:?}", entry.rtf, rtf);
+        }
+      }
+
+      match lossy.len() {
+        0 => Ok(()),
+        n => Err(RtfCreError::LossyEntries(n)),
+      }
+    },
+    CommandMode::Validate => {
+      let dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      let mut invalid = dict.invalid_outlines();
+      invalid.extend(dict.invalid_key_combos());
+      for (entry, err) in &invalid {
+        println!("{}: {:?}: {}", entry.steno, entry.translation, err);
+      }
+
+      match invalid.len() {
+        0 => Ok(()),
+        n => Err(RtfCreError::InvalidEntries(n)),
+      }
+    },
+    CommandMode::Conflicts => {
+      let dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      for conflict in boundary_conflicts(&dict) {
+        println!("{}: {:?}", conflict.entry.steno, conflict.entry.translation);
+        for (text, reading) in conflict.readings_with_text() {
+          let outlines: Vec<&str> = reading.iter().map(|entry| &*entry.steno).collect();
+          println!("  {}: {:?}", outlines.join(" "), text);
+        }
+      }
+
+      Ok(())
+    },
+    CommandMode::Translate => {
+      let dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      let mut translator = Translator::new(&dict);
+
+      let mut input = String::new();
+      io::stdin().read_to_string(&mut input)?;
+      for stroke in input.lines().flat_map(strokes_from_line) {
+        for event in translator.translate(&stroke) {
+          match event {
+            Event::Translate(tl) =>
+              println!("+ {} {:?}", tl.outline(), tl.english.as_deref().unwrap_or_default()),
+            Event::Undo(tl) =>
+              println!("- {} {:?}", tl.outline(), tl.english.as_deref().unwrap_or_default()),
+          }
+        }
+      }
+
+      Ok(())
+    },
+    CommandMode::Search => {
+      let pattern = match Pattern::new(args.kind, &args.pattern.unwrap()) {
+        Ok(pattern) => Ok(pattern),
+        Err(err) => Err(RtfCreError::InvalidPattern { err }),
       }?;
 
-      println!("{:?}", dict.len());
+      let dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      for entry in dict.search(&pattern, args.field) {
+        match entry.comment() {
+          Some(comment) =>
+            println!("{}: {:?} ({})", entry.steno, entry.translation, comment),
+          None => println!("{}: {:?}", entry.steno, entry.translation),
+        }
+      }
+
+      Ok(())
+    },
+    CommandMode::Diff => {
+      let old = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      let new = read_dictionary(&args.output.unwrap(), args.dialect, &parse_options)?;
+      let changes = diff(&old, &new);
+      for change in &changes {
+        println!("{}", describe_change(change));
+      }
+
+      if let Some(path) = args.patch {
+        let mut output = File::create(path)?;
+        writeln!(output, "{}", write_patch(&changes))?;
+      }
+
+      Ok(())
+    },
+    CommandMode::Patch => {
+      let mut contents = String::new();
+      File::open(args.patch.unwrap())?.read_to_string(&mut contents)?;
+      let changes = match parse_patch(&contents) {
+        Ok(changes) => Ok(changes),
+        Err(err) => Err(RtfCreError::InvalidPatch { err }),
+      }?;
+
+      let mut dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      let conflicts = apply_patch(&mut dict, &changes);
+      if !conflicts.is_empty() {
+        let mut stderr = StandardStream::stderr(ColorChoice::Always);
+        for Conflict { change, current } in &conflicts {
+          let found = match current {
+            Some((translation, _)) => format!("{:?}", translation),
+            None => "no entry".to_string(),
+          };
+          error(&mut stderr, format!("conflict: {} (found {})", describe_change(change), found))?;
+        }
+        return Err(RtfCreError::PatchConflicts(conflicts.len()));
+      }
+
+      let warnings = write_dictionary(dict, &args.output.unwrap(), &write_options, args.json_format)?;
+      report_dialect_warnings(warnings)?;
 
       Ok(())
     },
+    CommandMode::Merge => {
+      let base = read_dictionary(&args.base.unwrap(), args.dialect, &parse_options)?;
+      let ours = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      let theirs = read_dictionary(&args.theirs.unwrap(), args.dialect, &parse_options)?;
+      let output = args.output.unwrap();
+      let merged = merge(&base, &ours, &theirs);
+
+      let mut stderr = StandardStream::stderr(ColorChoice::Always);
+      for conflict in &merged.conflicts {
+        warning(&mut stderr, format!("{}: conflict between ours ({}) and theirs ({})",
+          conflict.steno, describe_entry(&conflict.ours), describe_entry(&conflict.theirs)))?;
+      }
+      if let Some(path) = args.conflicts {
+        let mut output = File::create(path)?;
+        writeln!(output, "{}", write_resolutions(&merged.conflicts))?;
+      }
+
+      let conflicts = merged.conflicts.len();
+      if args.conflict_markers {
+        if output.extension().and_then(|x| x.to_str()) != Some("rtf") {
+          return Err(RtfCreError::InvalidArgument);
+        }
+        let mut buf = Vec::new();
+        merged.write_conflict_markers(&mut buf)?;
+        let (encoded, _, _) = WINDOWS_1252.encode(from_utf8(&buf).unwrap());
+        File::create(output)?.write_all(&encoded)?;
+      } else {
+        report_dialect_warnings(
+          write_dictionary(merged.dict, &output, &write_options, args.json_format)?)?;
+      }
+
+      match conflicts {
+        0 => Ok(()),
+        n => Err(RtfCreError::MergeConflicts(n)),
+      }
+    },
+    CommandMode::Resolve => {
+      let mut contents = String::new();
+      File::open(args.conflicts.unwrap())?.read_to_string(&mut contents)?;
+
+      let mut dict = read_dictionary(&args.input, args.dialect, &parse_options)?;
+      let unresolved = match apply_resolutions(&mut dict, &contents) {
+        Ok(unresolved) => Ok(unresolved),
+        Err(err) => Err(RtfCreError::InvalidResolution { err }),
+      }?;
+
+      let mut stderr = StandardStream::stderr(ColorChoice::Always);
+      for steno in &unresolved {
+        warning(&mut stderr, format!("{}: no resolution given", steno))?;
+      }
+      let output = args.output.unwrap();
+      report_dialect_warnings(write_dictionary(dict, &output, &write_options, args.json_format)?)?;
+
+      match unresolved.len() {
+        0 => Ok(()),
+        n => Err(RtfCreError::MergeConflicts(n)),
+      }
+    },
   }
 }
 
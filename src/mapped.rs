@@ -0,0 +1,266 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use std::str;
+
+use memmap2::Mmap;
+
+use crate::dict::Dictionary;
+
+static MAGIC: &[u8; 8] = b"RTFCREMI";
+const VERSION: u32 = 1;
+
+// Magic, version, then the number of entries and translations and the
+// offsets of the sections that follow
+const HEADER_LEN: usize = 8 + 4 * 7;
+// The offset and length of a string, followed by the offset and length of
+// either the translation (for entries) or the list of outlines (for
+// translations)
+const RECORD_LEN: usize = 16;
+
+fn read_u32(data: &[u8], at: usize) -> usize {
+  let mut bytes = [0; 4];
+  bytes.copy_from_slice(&data[at..at + 4]);
+  u32::from_le_bytes(bytes) as usize
+}
+
+// Every offset and count in the index is 32 bits, which limits it to 4 GiB
+fn push_u32(buf: &mut Vec<u8>, value: usize) -> Result<(), io::Error> {
+  let value = u32::try_from(value).map_err(|_| io::Error::new(io::ErrorKind::InvalidData,
+    "dictionary is too large to index"))?;
+  buf.extend_from_slice(&value.to_le_bytes());
+  Ok(())
+}
+
+/// Write `dict` as an index that can be opened with `MappedDictionary`.
+/// Comments are not included.
+pub fn write_index(dict: &Dictionary, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+  let mut strings: Vec<u8> = vec![];
+  let mut add_string = |value: &str| {
+    let offset = strings.len();
+    strings.extend_from_slice(value.as_bytes());
+    (offset, value.len())
+  };
+  let system = add_string(&dict.cre_system);
+
+  // Both tables are sorted by their strings' bytes so they can be searched
+  let mut entries: Vec<(&str, &str)> = dict.entries.values()
+    .map(|entry| (&*entry.steno, &*entry.translation))
+    .collect();
+  entries.sort_unstable();
+  let mut forward = vec![];
+  for (steno, translation) in &entries {
+    let (steno_offset, steno_len) = add_string(steno);
+    let (tl_offset, tl_len) = add_string(translation);
+    for value in &[steno_offset, steno_len, tl_offset, tl_len] {
+      push_u32(&mut forward, *value)?;
+    }
+  }
+
+  let mut translations: Vec<(&str, Vec<usize>)> = dict.reverse_entries.iter()
+    .map(|(translation, outlines)| (&**translation, outlines.iter()
+      .filter_map(|steno| entries.binary_search_by(|(s, _)| s.cmp(&&**steno)).ok())
+      .collect()))
+    .collect();
+  translations.sort_unstable();
+  let mut reverse = vec![];
+  let mut outlines = vec![];
+  for (translation, ids) in &translations {
+    let (tl_offset, tl_len) = add_string(translation);
+    for value in &[tl_offset, tl_len, outlines.len() / 4, ids.len()] {
+      push_u32(&mut reverse, *value)?;
+    }
+    for id in ids {
+      push_u32(&mut outlines, *id)?;
+    }
+  }
+
+  let forward_start = HEADER_LEN;
+  let reverse_start = forward_start + forward.len();
+  let outlines_start = reverse_start + reverse.len();
+  let strings_start = outlines_start + outlines.len();
+  writer.write_all(MAGIC)?;
+  let mut header = vec![];
+  for value in &[VERSION as usize, entries.len(), translations.len(),
+      reverse_start, outlines_start, strings_start, system.1] {
+    push_u32(&mut header, *value)?;
+  }
+  writer.write_all(&header)?;
+  writer.write_all(&forward)?;
+  writer.write_all(&reverse)?;
+  writer.write_all(&outlines)?;
+  writer.write_all(&strings)
+}
+
+fn invalid(reason: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, format!("invalid dictionary index: {}", reason))
+}
+
+/// A read-only dictionary backed by a memory-mapped index written with
+/// `write_index`. Entries are read from the file as they are looked up rather
+/// than loaded onto the heap, and processes that open the same file share its
+/// pages.
+pub struct MappedDictionary {
+  data: Mmap,
+  entries: usize,
+  translations: usize,
+  reverse_start: usize,
+  outlines_start: usize,
+  strings_start: usize,
+  system_len: usize,
+}
+
+impl MappedDictionary {
+  /// Map the index at `path`. The file must not be modified while it is
+  /// open.
+  pub fn open(path: &Path) -> Result<Self, io::Error> {
+    let file = File::open(path)?;
+    // Safe as long as the file is not modified while mapped, which callers
+    // are required to ensure
+    let data = unsafe { Mmap::map(&file)? };
+    Self::from_mmap(data)
+  }
+
+  fn from_mmap(data: Mmap) -> Result<Self, io::Error> {
+    if data.len() < HEADER_LEN || &data[..8] != MAGIC {
+      return Err(invalid("not a dictionary index"));
+    }
+    if read_u32(&data, 8) as u32 != VERSION {
+      return Err(invalid("unsupported version"));
+    }
+    let header = |n: usize| read_u32(&data, 12 + 4 * n);
+    let dict = Self {
+      entries: header(0),
+      translations: header(1),
+      reverse_start: header(2),
+      outlines_start: header(3),
+      strings_start: header(4),
+      system_len: header(5),
+      data,
+    };
+    dict.check_sections()?;
+    Ok(dict)
+  }
+
+  // Only the section layout is checked on open, so that opening doesn't have
+  // to read the whole file. Records are checked as they are read.
+  fn check_sections(&self) -> Result<(), io::Error> {
+    let sections = [HEADER_LEN, self.reverse_start, self.outlines_start, self.strings_start];
+    if sections.windows(2).any(|pair| pair[0] > pair[1]) || self.strings_start > self.data.len()
+        || self.reverse_start - HEADER_LEN != self.entries * RECORD_LEN
+        || self.outlines_start - self.reverse_start != self.translations * RECORD_LEN {
+      return Err(invalid("sections out of bounds"));
+    }
+    Ok(())
+  }
+
+  /// Check every record of the index. Lookups never read out of bounds even
+  /// without this, but a corrupt record reads as an empty string or is
+  /// skipped, so call this to find out whether the whole file is intact.
+  pub fn validate(&self) -> Result<(), io::Error> {
+    let outline_count = self.outline_count();
+    let string_ok = |offset: usize, len: usize| self.string_range(offset, len)
+      .map_or(false, |range| str::from_utf8(&self.data[range]).is_ok());
+
+    if !string_ok(0, self.system_len) {
+      return Err(invalid("invalid system name"));
+    }
+    for i in 0..self.entries {
+      let record = self.record(HEADER_LEN, i);
+      if !string_ok(record[0], record[1]) || !string_ok(record[2], record[3]) {
+        return Err(invalid("invalid entry"));
+      }
+    }
+    for i in 0..self.translations {
+      let record = self.record(self.reverse_start, i);
+      if !string_ok(record[0], record[1]) || record[2] + record[3] > outline_count {
+        return Err(invalid("invalid translation"));
+      }
+    }
+    if (0..outline_count).any(|i| self.outline(i).is_none()) {
+      return Err(invalid("entry ID out of range"));
+    }
+    Ok(())
+  }
+
+  fn outline_count(&self) -> usize {
+    (self.strings_start - self.outlines_start) / 4
+  }
+
+  // The ID of the `index`th entry in the list of outlines, if both are in
+  // range
+  fn outline(&self, index: usize) -> Option<usize> {
+    if index >= self.outline_count() {
+      return None;
+    }
+    Some(read_u32(&self.data, self.outlines_start + 4 * index))
+      .filter(|id| *id < self.entries)
+  }
+
+  fn string_range(&self, offset: usize, len: usize) -> Option<Range<usize>> {
+    let start = self.strings_start.checked_add(offset)?;
+    let end = start.checked_add(len)?;
+    if end <= self.data.len() { Some(start..end) } else { None }
+  }
+
+  fn string(&self, offset: usize, len: usize) -> &str {
+    self.string_range(offset, len)
+      .and_then(|range| str::from_utf8(&self.data[range]).ok())
+      .unwrap_or("")
+  }
+
+  fn record(&self, start: usize, index: usize) -> [usize; 4] {
+    let at = start + index * RECORD_LEN;
+    [0, 1, 2, 3].map(|n| read_u32(&self.data, at + 4 * n))
+  }
+
+  // Binary search a table of records by the string each one starts with
+  fn find(&self, start: usize, len: usize, key: &str) -> Option<[usize; 4]> {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+      let mid = (low + high) / 2;
+      let record = self.record(start, mid);
+      match self.string(record[0], record[1]).cmp(key) {
+        Ordering::Less => low = mid + 1,
+        Ordering::Greater => high = mid,
+        Ordering::Equal => return Some(record),
+      }
+    }
+    None
+  }
+
+  pub fn cre_system(&self) -> &str {
+    self.string(0, self.system_len)
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries == 0
+  }
+
+  pub fn contains_key(&self, steno: &str) -> bool {
+    self.find(HEADER_LEN, self.entries, steno).is_some()
+  }
+
+  pub fn lookup(&self, steno: &str) -> Option<String> {
+    let record = self.find(HEADER_LEN, self.entries, steno)?;
+    Some(self.string(record[2], record[3]).to_string())
+  }
+
+  pub fn rev_lookup(&self, translation: &str) -> Option<Vec<String>> {
+    let record = self.find(self.reverse_start, self.translations, translation)?;
+    Some((record[2]..record[2].saturating_add(record[3]))
+      .map_while(|i| self.outline(i))
+      .map(|id| {
+        let entry = self.record(HEADER_LEN, id);
+        self.string(entry[0], entry[1]).to_string()
+      })
+      .collect())
+  }
+}
@@ -0,0 +1,213 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+use serde_json::{Map, Value};
+
+use crate::dict::{Dictionary, Entry};
+use crate::error::Error;
+use crate::translation::format_plover_to_rtf;
+
+/// An outline changed in different ways on both sides of a merge. Each side
+/// is None if the outline is not defined there.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MergeConflict {
+  pub steno: String,
+  pub base: Option<Entry>,
+  pub ours: Option<Entry>,
+  pub theirs: Option<Entry>,
+}
+
+/// The result of a three-way merge. Conflicting outlines keep our version in
+/// `dict` until they are resolved.
+#[derive(Debug)]
+pub struct Merge {
+  pub dict: Dictionary,
+  pub conflicts: Vec<MergeConflict>,
+}
+
+fn merge_value<T: PartialEq + Clone>(base: &T, ours: &T, theirs: &T) -> Option<T> {
+  if ours == theirs || theirs == base {
+    Some(ours.clone())
+  } else if ours == base {
+    Some(theirs.clone())
+  } else {
+    None
+  }
+}
+
+// The merged entry for an outline, None if it should not be defined, or an
+// error if the two sides conflict
+fn merge_entry(steno: &str, base: Option<&Entry>, ours: Option<&Entry>, theirs: Option<&Entry>)
+    -> Result<Option<Entry>, ()> {
+  if let Some(merged) = merge_value(&base, &ours, &theirs) {
+    return Ok(merged.cloned());
+  }
+
+  // Both sides define the outline differently, but may have changed
+  // different parts of the entry
+  if let (Some(ours), Some(theirs)) = (ours, theirs) {
+    let base_translation = base.map(|entry| entry.translation.clone());
+    let translation = merge_value(
+      &base_translation, &Some(ours.translation.clone()), &Some(theirs.translation.clone()));
+    let comment = merge_value(
+      &base.and_then(Entry::comment), &ours.comment(), &theirs.comment());
+    if let (Some(Some(translation)), Some(comment)) = (translation, comment) {
+      return Ok(Some(Entry::new(steno, &translation, comment.as_deref())));
+    }
+  }
+
+  Err(())
+}
+
+/// Merge the changes made to `base` in `ours` and in `theirs`. Translations
+/// and comments are merged separately, so an outline retranslated on one
+/// side and re-commented on the other does not conflict. Entries are kept in
+/// our order, followed by those only defined in theirs.
+pub fn merge(base: &Dictionary, ours: &Dictionary, theirs: &Dictionary) -> Merge {
+  let mut dict = Dictionary::new(&ours.cre_system);
+  let mut conflicts = vec![];
+
+  let mut seen = HashSet::new();
+  let outlines = ours.entries.keys()
+    .chain(theirs.entries.keys())
+    .chain(base.entries.keys());
+  for steno in outlines {
+    if !seen.insert(steno) {
+      continue;
+    }
+    let (base_entry, our_entry, their_entry) =
+      (base.entry(steno), ours.entry(steno), theirs.entry(steno));
+    let merged = merge_entry(steno, base_entry, our_entry, their_entry).unwrap_or_else(|_| {
+      conflicts.push(MergeConflict {
+        steno: steno.to_string(),
+        base: base_entry.cloned(),
+        ours: our_entry.cloned(),
+        theirs: their_entry.cloned(),
+      });
+      our_entry.cloned()
+    });
+    if let Some(entry) = merged {
+      dict.add_entry(entry.steno.to_string(), entry.translation.to_string(), entry.comment());
+    }
+  }
+
+  Merge { dict, conflicts }
+}
+
+fn choice(entry: &Option<Entry>) -> String {
+  format!("{{\\cxc {}}}", match entry {
+    Some(entry) => format_plover_to_rtf(&entry.translation),
+    None => String::new(),
+  })
+}
+
+impl Merge {
+  /// Write the merged dictionary as RTF, with each conflicting outline
+  /// written as a `\cxconf` group offering our and their translations.
+  pub fn write_conflict_markers(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+    let markers: HashMap<&str, String> = self.conflicts.iter()
+      .map(|conflict| (conflict.steno.as_str(),
+        format!("{{\\cxconf [{}|{}]}}", choice(&conflict.ours), choice(&conflict.theirs))))
+      .collect();
+
+    // Outlines we deleted are not in the merged dictionary, but still need
+    // their conflicts written out
+    let deleted: Vec<Entry> = self.conflicts.iter()
+      .filter(|conflict| conflict.ours.is_none())
+      .map(|conflict| Entry::new(&conflict.steno, "", None))
+      .collect();
+
+    let entries = self.dict.entries.values().chain(deleted.iter())
+      .map(|entry| (entry, match markers.get(&*entry.steno) {
+        Some(marker) => marker.clone(),
+        None => format_plover_to_rtf(&entry.translation),
+      }));
+    self.dict.write_formatted(writer, entries)
+  }
+}
+
+fn entry_to_json(entry: &Option<Entry>) -> Value {
+  match entry {
+    Some(entry) => {
+      let mut map = Map::new();
+      map.insert("translation".to_string(), Value::from(&*entry.translation));
+      map.insert("comment".to_string(), match entry.comment() {
+        Some(comment) => Value::String(comment),
+        None => Value::Null,
+      });
+      Value::Object(map)
+    },
+    None => Value::Null,
+  }
+}
+
+/// Write the conflicts as a JSON resolution file. Each conflict has a
+/// `resolution` to be filled in with "base", "ours" or "theirs", or an object
+/// with a translation and comment, before passing it to `apply_resolutions`.
+pub fn write_resolutions(conflicts: &[MergeConflict]) -> String {
+  let conflicts = conflicts.iter().map(|conflict| {
+    let mut map = Map::new();
+    map.insert("steno".to_string(), Value::from(conflict.steno.as_str()));
+    map.insert("base".to_string(), entry_to_json(&conflict.base));
+    map.insert("ours".to_string(), entry_to_json(&conflict.ours));
+    map.insert("theirs".to_string(), entry_to_json(&conflict.theirs));
+    map.insert("resolution".to_string(), Value::Null);
+    Value::Object(map)
+  }).collect();
+  serde_json::to_string_pretty(&Value::Array(conflicts)).unwrap()
+}
+
+enum Resolution {
+  Unresolved,
+  Remove,
+  Entry(String, Option<String>),
+}
+
+fn resolution(conflict: &Map<String, Value>) -> Result<Resolution, String> {
+  let entry = |value: &Value| match value {
+    Value::Null => Ok(Resolution::Remove),
+    Value::Object(entry) => match (entry.get("translation"), entry.get("comment")) {
+      (Some(Value::String(tl)), Some(Value::String(comment))) =>
+        Ok(Resolution::Entry(tl.clone(), Some(comment.clone()))),
+      (Some(Value::String(tl)), Some(Value::Null)) | (Some(Value::String(tl)), None) =>
+        Ok(Resolution::Entry(tl.clone(), None)),
+      _ => Err("expected a translation and optional comment".to_string()),
+    },
+    _ => Err("expected an entry or null".to_string()),
+  };
+
+  match conflict.get("resolution") {
+    Some(Value::String(side)) if side == "base" || side == "ours" || side == "theirs" =>
+      entry(conflict.get(side.as_str()).unwrap_or(&Value::Null)),
+    Some(value @ Value::Object(_)) => entry(value),
+    Some(Value::Null) | None => Ok(Resolution::Unresolved),
+    Some(_) => Err("expected \"base\", \"ours\", \"theirs\" or an entry".to_string()),
+  }
+}
+
+/// Apply a resolution file written by `write_resolutions` to a merged
+/// dictionary, returning the outlines that are still unresolved.
+pub fn apply_resolutions(dict: &mut Dictionary, resolutions: &str) -> Result<Vec<String>, Error> {
+  let invalid = |reason: String| Error::InvalidResolution { reason };
+  let conflicts = match serde_json::from_str(resolutions) {
+    Ok(Value::Array(conflicts)) => conflicts,
+    Ok(_) => return Err(invalid("expected a list of conflicts".to_string())),
+    Err(err) => return Err(invalid(err.to_string())),
+  };
+
+  let mut unresolved = vec![];
+  for conflict in &conflicts {
+    let conflict = conflict.as_object()
+      .ok_or_else(|| invalid("expected a conflict object".to_string()))?;
+    let steno = match conflict.get("steno") {
+      Some(Value::String(steno)) => steno.clone(),
+      _ => return Err(invalid("expected a string for \"steno\"".to_string())),
+    };
+    match resolution(conflict).map_err(|reason| invalid(format!("{}: {}", steno, reason)))? {
+      Resolution::Entry(translation, comment) => dict.add_entry(steno, translation, comment),
+      Resolution::Remove => dict.remove_entry(steno),
+      Resolution::Unresolved => unresolved.push(steno),
+    }
+  }
+  Ok(unresolved)
+}
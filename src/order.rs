@@ -0,0 +1,83 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::dialect::Dialect;
+use crate::dict::Entry;
+use crate::system::{NumberKey, StenoSystem};
+
+/// The order to write the entries of a dictionary in.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Order {
+  /// The order the entries were added in.
+  #[default]
+  Original,
+  /// Steno order, stroke by stroke.
+  Steno,
+  /// Alphabetically by translation.
+  Translation,
+  /// By the number of strokes in the outline.
+  StrokeCount,
+}
+
+#[derive(Debug)]
+pub struct OrderParseError {
+  value: String,
+}
+
+impl fmt::Display for OrderParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "unknown order {:?}", self.value)
+  }
+}
+
+impl FromStr for Order {
+  type Err = OrderParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "original" => Ok(Self::Original),
+      "steno" => Ok(Self::Steno),
+      "translation" => Ok(Self::Translation),
+      "strokes" | "stroke-count" => Ok(Self::StrokeCount),
+      _ => Err(OrderParseError { value: s.to_string() }),
+    }
+  }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct WriteOptions {
+  pub order: Order,
+  pub dialect: Dialect,
+  /// How to write strokes that use the number key.
+  pub number_key: NumberKey,
+}
+
+/// A key that sorts English stenotype outlines in steno order, stroke by
+/// stroke. See `StenoSystem::steno_sort_key` for other systems.
+pub fn steno_sort_key(steno: &str) -> Vec<Vec<usize>> {
+  StenoSystem::english().steno_sort_key(steno)
+}
+
+fn stroke_count(steno: &str) -> usize {
+  steno.split('/').count()
+}
+
+/// Sort entries in the given order, using the steno order of `system`.
+/// Entries that compare equal, such as those with the same translation, are
+/// ordered by steno.
+pub fn sort_entries(entries: &mut [&Entry], order: Order, system: &StenoSystem) {
+  let steno_sort_key = |steno: &str| system.steno_sort_key(steno);
+  match order {
+    Order::Original => {},
+    Order::Steno => entries.sort_by_cached_key(|entry| steno_sort_key(&entry.steno)),
+    Order::Translation => entries.sort_by_cached_key(|entry| (
+      entry.translation.to_lowercase(),
+      entry.translation.clone(),
+      steno_sort_key(&entry.steno),
+    )),
+    Order::StrokeCount => entries.sort_by_cached_key(|entry| (
+      stroke_count(&entry.steno),
+      steno_sort_key(&entry.steno),
+    )),
+  }
+}
@@ -1,12 +1,21 @@
-use crate::dict::Dictionary;
-use crate::rtf::parse_file as parse_file;
+use crate::cache::parse_rtf_cached_with_options;
+use crate::conflicts::boundary_conflicts;
+use crate::dict::{Dictionary, Entry};
+use crate::merge::{apply_resolutions, merge as merge_dictionaries, write_resolutions};
+use crate::order::{Order, WriteOptions};
+use crate::rtf::{ParseOptions, parse_file_with_options};
+use crate::search::{Pattern, PatternKind, SearchField};
+use crate::stats::stats;
+use crate::system::{NumberKey, StenoSystem};
 
 use std::collections::HashMap;
-use std::io::Read;
+use std::io::{Read, Write};
+use std::path::Path;
 
 use pyo3::prelude::*;
-use pyo3::class::{PyMappingProtocol, PySequenceProtocol};
-use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::class::{PyContextProtocol, PyMappingProtocol, PySequenceProtocol};
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::types::{PyDict, PyType};
 use pyo3::wrap_pyfunction;
 use pyo3_file::PyFileLikeObject;
 
@@ -34,15 +43,20 @@ impl RtfDictionary {
     Ok(())
   }
 
-  /// dump(self, file, /)
+  /// dump(self, file, /, order="original", number_key="keep")
   /// --
   ///
   /// Write the contents of the dictionary to `file`, a file-like object.
-  /// `file` should be opened in binary mode.
-  fn dump(&self, file: PyObject) -> PyResult<()> {
+  /// `file` should be opened in binary mode. `order` is one of "original",
+  /// "steno", "translation" or "strokes". `number_key` is one of "keep",
+  /// "digits", "explicit" or "strip", and sets how strokes that use the
+  /// number key are written.
+  #[args(order = "\"original\"", number_key = "\"keep\"")]
+  fn dump(&self, file: PyObject, order: &str, number_key: &str) -> PyResult<()> {
+    let options = write_options(order, number_key)?;
     match PyFileLikeObject::with_requirements(file, true, true, true) {
       Ok(mut f) => {
-        match self.dict.write(&mut f) {
+        match self.dict.write_with_options(&mut f, &options) {
           Ok(_) => Ok(()),
           Err(err) => Err(PyValueError::new_err(format!("failed to write RTF dictionary: {:?}", err))),
         }
@@ -51,24 +65,77 @@ impl RtfDictionary {
     }
   }
 
-  /// dumps(self, /)
+  /// dumps(self, /, order="original", number_key="keep")
   /// --
   ///
   /// Write the contents of the dictionary to a string and return the string.
-  fn dumps(&self) -> PyResult<String> {
+  /// `order` and `number_key` are as for `dump`.
+  #[args(order = "\"original\"", number_key = "\"keep\"")]
+  fn dumps(&self, order: &str, number_key: &str) -> PyResult<String> {
+    let options = write_options(order, number_key)?;
     let mut buf: Vec<u8> = vec![];
-    match self.dict.write(&mut buf) {
+    match self.dict.write_with_options(&mut buf, &options) {
       Ok(_) => Ok(String::from_utf8(buf)?),
       Err(err) => Err(PyValueError::new_err(format!("failed to write RTF dictionary: {:?}", err))),
     }
   }
 
+  /// resolve(self, file, /)
+  /// --
+  ///
+  /// Apply a resolution file written by `merge` and filled in to this merged
+  /// dictionary, returning the outlines that are still unresolved. `file`
+  /// should be opened in binary mode.
+  fn resolve(&mut self, file: PyObject) -> PyResult<Vec<String>> {
+    let mut f = PyFileLikeObject::with_requirements(file, true, false, false)?;
+    let mut contents = String::new();
+    f.read_to_string(&mut contents)
+      .map_err(|err| PyValueError::new_err(format!("failed to read conflicts: {:?}", err)))?;
+    apply_resolutions(&mut self.dict, &contents)
+      .map_err(|err| PyValueError::new_err(format!("{}", err)))
+  }
+
   #[getter]
   /// The number of strokes of the longest stroke defined in the dictionary.
   fn longest_key(&self) -> PyResult<usize> {
     Ok(self.dict.longest_key)
   }
 
+  #[getter]
+  /// The steno system outlines are validated and sorted with, as it was set:
+  /// "english", "palantype", or the path of the JSON file defining it.
+  fn steno_system(&self) -> PyResult<String> {
+    Ok(self.dict.steno_system.id().to_string())
+  }
+
+  #[setter]
+  /// Set the steno system to "english", "palantype", or the path of a JSON
+  /// file defining a system.
+  fn set_steno_system(&mut self, system: &str) -> PyResult<()> {
+    self.dict.steno_system = StenoSystem::find(system)
+      .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+    Ok(())
+  }
+
+  /// normalize_outlines(self, /)
+  /// --
+  ///
+  /// Store outlines in canonical form for the steno system from now on, so
+  /// that outlines such as "KA-T" and "KAT" are the same entry, and rewrite
+  /// the outlines already in the dictionary. Return a list of (steno,
+  /// replaced, kept) tuples for the entries merged because their outlines
+  /// became the same, where `replaced` and `kept` are (steno, translation)
+  /// tuples with the outlines as they were before.
+  fn normalize_outlines(&mut self) -> PyResult<Vec<(String, (String, String), (String, String))>> {
+    Ok(self.dict.normalize_outlines().into_iter()
+      .map(|merged| (
+        merged.steno,
+        (merged.replaced.steno.to_string(), merged.replaced.translation.to_string()),
+        (merged.kept.steno.to_string(), merged.kept.translation.to_string()),
+      ))
+      .collect())
+  }
+
   /// lookup(self, steno, /)
   /// --
   ///
@@ -76,7 +143,7 @@ impl RtfDictionary {
   /// stroke, or None if not available.
   fn lookup(&self, steno: &str) -> PyResult<Option<(String, Option<String>)>> {
     match self.dict.entry(steno) {
-      Some(entry) => Ok(Some((entry.translation.clone(), entry.comment()))),
+      Some(entry) => Ok(Some((entry.translation.to_string(), entry.comment()))),
       None => Ok(None),
     }
   }
@@ -92,18 +159,122 @@ impl RtfDictionary {
     }
   }
 
+  /// casereverse_lookup(self, translation, /)
+  /// --
+  ///
+  /// Return the list of translations that differ from `translation` only in
+  /// case, as in Plover's dictionary API.
+  fn casereverse_lookup(&self, translation: &str) -> PyResult<Vec<String>> {
+    Ok(self.dict.casereverse_lookup(translation))
+  }
+
+  /// normalized_reverse_lookup(self, translation, /)
+  /// --
+  ///
+  /// Return the list of steno strokes whose translations match `translation`
+  /// when case and formatting operators such as attachment are ignored.
+  fn normalized_reverse_lookup(&self, translation: &str) -> PyResult<Vec<String>> {
+    Ok(self.dict.normalized_rev_lookup(translation))
+  }
+
+  /// search(self, pattern, /, field="translation", kind="substring")
+  /// --
+  ///
+  /// Return a list of (steno, translation) tuples for the entries whose
+  /// `field` ("outline", "translation" or "comment") matches `pattern`, in
+  /// file order. `kind` is one of "substring", "glob" or "regex".
+  #[args(field = "\"translation\"", kind = "\"substring\"")]
+  fn search(&self, pattern: &str, field: &str, kind: &str) -> PyResult<Vec<(String, String)>> {
+    let field: SearchField = field.parse()
+      .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+    let kind: PatternKind = kind.parse()
+      .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+    let pattern = Pattern::new(kind, pattern)
+      .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+    Ok(self.dict.search(&pattern, field).into_iter()
+      .map(|entry| (entry.steno.to_string(), entry.translation.to_string()))
+      .collect())
+  }
+
+  /// stats(self, /, top=10)
+  /// --
+  ///
+  /// Return a dict of statistics about the dictionary: the number of
+  /// "entries", the "longest_key", the number of outlines with each number of
+  /// strokes ("stroke_counts"), the number of entries of each kind ("kinds"),
+  /// the number of "commented" entries and "comment_coverage", and a list of
+  /// up to `top` (translation, count) tuples for the translations with the
+  /// most outlines ("most_outlines").
+  #[args(top = "10")]
+  fn stats(&self, py: Python, top: usize) -> PyResult<PyObject> {
+    let stats = stats(&self.dict, top);
+    let kinds: HashMap<&str, usize> = stats.kinds.iter()
+      .map(|(kind, count)| (kind.name(), *count))
+      .collect();
+    let result = PyDict::new(py);
+    result.set_item("entries", stats.entries)?;
+    result.set_item("longest_key", stats.longest_key)?;
+    result.set_item("stroke_counts", stats.stroke_counts.clone())?;
+    result.set_item("kinds", kinds)?;
+    result.set_item("commented", stats.commented)?;
+    result.set_item("comment_coverage", stats.comment_coverage())?;
+    result.set_item("most_outlines", stats.most_outlines)?;
+    Ok(result.to_object(py))
+  }
+
+  /// invalid_outlines(self, /)
+  /// --
+  ///
+  /// Return a list of (steno, reason) tuples for the entries whose outlines
+  /// are not valid steno, such as keys out of steno order, in file order.
+  fn invalid_outlines(&self) -> PyResult<Vec<(String, String)>> {
+    Ok(self.dict.invalid_outlines().into_iter()
+      .map(|(entry, err)| (entry.steno.to_string(), format!("{}", err)))
+      .collect())
+  }
+
+  /// boundary_conflicts(self, /)
+  /// --
+  ///
+  /// Return a list of (steno, translation, readings) tuples for the
+  /// multi-stroke entries whose strokes can also be read as shorter outlines
+  /// defined on their own, in file order. Each reading is a (text, entries)
+  /// tuple of the text it writes, formatted as Plover would write it, and a
+  /// list of (steno, translation) tuples for the outlines it is made of.
+  fn boundary_conflicts(&self)
+      -> PyResult<Vec<(String, String, Vec<(String, Vec<(String, String)>)>)>> {
+    Ok(boundary_conflicts(&self.dict).into_iter()
+      .map(|conflict| (
+        conflict.entry.steno.to_string(),
+        conflict.entry.translation.to_string(),
+        conflict.readings_with_text().into_iter()
+          .map(|(text, reading)| (text, reading.iter()
+            .map(|entry| (entry.steno.to_string(), entry.translation.to_string()))
+            .collect()))
+          .collect()))
+      .collect())
+  }
+
+  /// invalid_key_combos(self, /)
+  /// --
+  ///
+  /// Return a list of (steno, reason) tuples for the entries with key
+  /// combinations that are not valid Plover syntax, in file order.
+  fn invalid_key_combos(&self) -> PyResult<Vec<(String, String)>> {
+    Ok(self.dict.invalid_key_combos().into_iter()
+      .map(|(entry, err)| (entry.steno.to_string(), format!("{}", err)))
+      .collect())
+  }
+
   /// add_comment(self, steno, comment, /)
   /// --
   ///
   /// Add a comment to the entry for the given steno stroke, or raise a
   /// KeyError if not available.
   fn add_comment(&mut self, steno: &str, comment: &str) -> PyResult<()> {
-    match self.dict.entry_mut(steno) {
-      Some(entry) => {
-        entry.add_comment(comment);
-        Ok(())
-      },
-      None => Err(PyKeyError::new_err(steno.to_string())),
+    match self.dict.set_comment(steno, Some(comment)) {
+      true => Ok(()),
+      false => Err(PyKeyError::new_err(steno.to_string())),
     }
   }
 
@@ -113,20 +284,70 @@ impl RtfDictionary {
   /// Remove the comment from the entry for the given steno stroke, or raise a
   /// KeyError if not available.
   fn remove_comment(&mut self, steno: &str) -> PyResult<()> {
-    match self.dict.entry_mut(steno) {
-      Some(entry) => {
-        entry.remove_comment();
-        Ok(())
-      },
-      None => Err(PyKeyError::new_err(steno.to_string())),
+    match self.dict.set_comment(steno, None) {
+      true => Ok(()),
+      false => Err(PyKeyError::new_err(steno.to_string())),
     }
   }
 
+  /// transaction(self, /)
+  /// --
+  ///
+  /// Return a context manager that groups the changes made inside it into a
+  /// single transaction. The changes are rolled back if an exception is
+  /// raised, and can otherwise be undone together with `undo`.
+  fn transaction(slf: PyRef<Self>) -> Transaction {
+    Transaction { dict: slf.into() }
+  }
+
+  /// begin(self, /)
+  /// --
+  ///
+  /// Start a transaction. Transactions can be nested.
+  fn begin(&mut self) -> PyResult<()> {
+    self.dict.begin();
+    Ok(())
+  }
+
+  /// commit(self, /)
+  /// --
+  ///
+  /// Commit the innermost transaction.
+  fn commit(&mut self) -> PyResult<()> {
+    self.dict.commit().map_err(|err| PyRuntimeError::new_err(format!("{}", err)))
+  }
+
+  /// rollback(self, /)
+  /// --
+  ///
+  /// Revert the changes made in the innermost transaction.
+  fn rollback(&mut self) -> PyResult<()> {
+    self.dict.rollback().map_err(|err| PyRuntimeError::new_err(format!("{}", err)))
+  }
+
+  /// undo(self, /)
+  /// --
+  ///
+  /// Undo the last committed transaction. Return False if there was nothing
+  /// to undo.
+  fn undo(&mut self) -> PyResult<bool> {
+    self.dict.undo().map_err(|err| PyRuntimeError::new_err(format!("{}", err)))
+  }
+
+  /// redo(self, /)
+  /// --
+  ///
+  /// Redo the last undone transaction. Return False if there was nothing to
+  /// redo.
+  fn redo(&mut self) -> PyResult<bool> {
+    self.dict.redo().map_err(|err| PyRuntimeError::new_err(format!("{}", err)))
+  }
+
   #[getter]
   /// A dictionary mapping steno strokes to translations.
   fn stroke_to_translation(&self) -> PyResult<HashMap<String, String>> {
     Ok(self.dict.entries.iter()
-      .map(|(k, v)| (k.clone(), v.translation.clone()))
+      .map(|(k, v)| (k.to_string(), v.translation.to_string()))
       .collect::<HashMap<String, String>>())
   }
 
@@ -135,7 +356,7 @@ impl RtfDictionary {
   /// translate to them.
   fn translation_to_strokes(&self) -> PyResult<HashMap<String, Vec<String>>> {
     Ok(self.dict.reverse_entries.iter()
-      .map(|(k, v)| (k.clone(), v.iter().cloned().collect::<Vec<String>>()))
+      .map(|(k, v)| (k.to_string(), v.iter().map(|steno| steno.to_string()).collect::<Vec<String>>()))
       .collect::<HashMap<String, Vec<String>>>())
   }
 }
@@ -162,6 +383,33 @@ impl PyMappingProtocol for RtfDictionary {
   }
 }
 
+#[pyclass]
+pub struct Transaction {
+  dict: Py<RtfDictionary>,
+}
+
+#[pyproto]
+impl<'p> PyContextProtocol<'p> for Transaction {
+  fn __enter__(&mut self) -> PyResult<Py<RtfDictionary>> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    self.dict.as_ref(py).try_borrow_mut()?.dict.begin();
+    Ok(self.dict.clone_ref(py))
+  }
+
+  fn __exit__(&mut self, ty: Option<&'p PyType>, _value: Option<&'p PyAny>,
+      _traceback: Option<&'p PyAny>) -> PyResult<bool> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let mut dict = self.dict.as_ref(py).try_borrow_mut()?;
+    match ty {
+      Some(_) => dict.dict.rollback(),
+      None => dict.dict.commit(),
+    }.map_err(|err| PyRuntimeError::new_err(format!("{}", err)))?;
+    Ok(false)
+  }
+}
+
 #[pyproto]
 impl PySequenceProtocol for RtfDictionary {
   fn __contains__(&self, steno: &str) -> PyResult<bool> {
@@ -169,19 +417,31 @@ impl PySequenceProtocol for RtfDictionary {
   }
 }
 
-#[pyfunction]
-/// load(file, /)
+#[pyfunction(cache = "None", number_key = "\"keep\"")]
+/// load(file, /, cache=None, number_key="keep")
 /// --
 ///
 /// Read the contents of `file`, a file-like object containing an RTF
 /// dictionary, into a Python object. `file` should be opened in binary mode.
-fn load(file: PyObject) -> PyResult<RtfDictionary> {
+/// If `cache` is the path of a cache file written for the same contents, the
+/// dictionary is read from it instead of parsed; otherwise the cache is
+/// rebuilt for next time. `number_key` is one of "keep", "digits",
+/// "explicit" or "strip", and sets how strokes that use the number key are
+/// read; with "keep", they are read exactly as written.
+fn load(file: PyObject, cache: Option<String>, number_key: &str) -> PyResult<RtfDictionary> {
+  let options = parse_options(number_key)?;
   match PyFileLikeObject::with_requirements(file, true, false, true) {
     Ok(mut f) => {
       let mut contents = String::new();
       let _ = f.read_to_string(&mut contents);
-      match parse_file(&contents) {
-        Ok((_, dict)) => Ok(RtfDictionary { dict }),
+      if let Some(cache) = cache {
+        return match parse_rtf_cached_with_options(&contents, Path::new(&cache), &options) {
+          Some(dict) => Ok(RtfDictionary { dict }),
+          None => Err(PyValueError::new_err("failed to read RTF dictionary")),
+        };
+      }
+      match parse_file_with_options(&contents, &options, None) {
+        Ok((_, parsed)) => Ok(RtfDictionary { dict: parsed.dict }),
         Err(err) => Err(PyValueError::new_err(format!("failed to read RTF dictionary: {:?}", err))),
       }
     },
@@ -189,19 +449,79 @@ fn load(file: PyObject) -> PyResult<RtfDictionary> {
   }
 }
 
-#[pyfunction]
-/// loads(string, /)
+#[pyfunction(number_key = "\"keep\"")]
+/// loads(string, /, number_key="keep")
 /// --
 ///
 /// Read the contents of `string`, a string or string-like containing an RTF
-/// dictionary, into a Python object.
-fn loads(string: &str) -> PyResult<RtfDictionary> {
-  match parse_file(&string) {
-    Ok((_, dict)) => Ok(RtfDictionary { dict }),
+/// dictionary, into a Python object. `number_key` is as for `load`.
+fn loads(string: &str, number_key: &str) -> PyResult<RtfDictionary> {
+  let options = parse_options(number_key)?;
+  match parse_file_with_options(&string, &options, None) {
+    Ok((_, parsed)) => Ok(RtfDictionary { dict: parsed.dict }),
     Err(err) => Err(PyValueError::new_err(format!("failed to read RTF dictionary: {:?}", err))),
   }
 }
 
+fn parse_number_key(number_key: &str) -> PyResult<NumberKey> {
+  number_key.parse()
+    .map_err(|err| PyValueError::new_err(format!("{}", err)))
+}
+
+fn parse_options(number_key: &str) -> PyResult<ParseOptions> {
+  Ok(ParseOptions { number_key: parse_number_key(number_key)?, ..ParseOptions::default() })
+}
+
+fn write_options(order: &str, number_key: &str) -> PyResult<WriteOptions> {
+  let order: Order = order.parse()
+    .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+  Ok(WriteOptions { order, number_key: parse_number_key(number_key)?, ..WriteOptions::default() })
+}
+
+// An entry as a (translation, comment) tuple, or None if not defined
+type EntryTuple = Option<(String, Option<String>)>;
+
+fn entry_tuple(entry: &Option<Entry>) -> EntryTuple {
+  entry.as_ref().map(|entry| (entry.translation.to_string(), entry.comment()))
+}
+
+#[pyfunction(conflicts = "None", conflict_markers = "None")]
+/// merge(base, ours, theirs, /, conflicts=None, conflict_markers=None)
+/// --
+///
+/// Merge the changes made to `base` in `ours` and in `theirs`, returning a
+/// tuple of the merged dictionary and a list of conflicts. Each conflict is a
+/// tuple (steno, base, ours, theirs) where each side is a (translation,
+/// comment) tuple, or None if the outline is not defined there. Conflicting
+/// outlines keep the version in `ours` in the merged dictionary.
+///
+/// If `conflicts` is a file-like object, a resolution file for the conflicts
+/// is written to it, to be filled in and passed to `resolve`. If
+/// `conflict_markers` is a file-like object, the merged dictionary is written
+/// to it with each conflicting outline as a `\cxconf` group offering both
+/// translations. Both should be opened in binary mode.
+fn merge(base: PyRef<RtfDictionary>, ours: PyRef<RtfDictionary>, theirs: PyRef<RtfDictionary>,
+    conflicts: Option<PyObject>, conflict_markers: Option<PyObject>)
+    -> PyResult<(RtfDictionary, Vec<(String, EntryTuple, EntryTuple, EntryTuple)>)> {
+  let merged = merge_dictionaries(&base.dict, &ours.dict, &theirs.dict);
+  if let Some(file) = conflicts {
+    let mut f = PyFileLikeObject::with_requirements(file, false, true, false)?;
+    f.write_all(write_resolutions(&merged.conflicts).as_bytes())
+      .map_err(|err| PyValueError::new_err(format!("failed to write conflicts: {:?}", err)))?;
+  }
+  if let Some(file) = conflict_markers {
+    let mut f = PyFileLikeObject::with_requirements(file, false, true, false)?;
+    merged.write_conflict_markers(&mut f)
+      .map_err(|err| PyValueError::new_err(format!("failed to write RTF dictionary: {:?}", err)))?;
+  }
+
+  let conflicts = merged.conflicts.iter()
+    .map(|conflict| (conflict.steno.clone(), entry_tuple(&conflict.base),
+      entry_tuple(&conflict.ours), entry_tuple(&conflict.theirs)))
+    .collect();
+  Ok((RtfDictionary { dict: merged.dict }, conflicts))
+}
+
 #[pymodule]
 /// RTF/CRE (Rich Text Format with Court Reporting Extensions) is an application
 /// of Microsoft's Rich Text Format in court reporting and related professions.
@@ -246,6 +566,8 @@ fn loads(string: &str) -> PyResult<RtfDictionary> {
 ///     False
 ///     >>> dict.reverse_lookup("cat")
 ///     ["KAT"]
+///     >>> dict.search("ca")
+///     [("KAT", "cat")]
 ///
 /// Accessing entry comments:
 ///
@@ -255,10 +577,31 @@ fn loads(string: &str) -> PyResult<RtfDictionary> {
 ///     "TK means D"
 ///     >>> dict.remove_comment("TKOG")
 ///
+/// Editing in transactions:
+///
+///     >>> with dict.transaction():
+///     ...     dict["KAT"] = "Cat"
+///     ...     del dict["TKOG"]
+///     >>> dict.undo()
+///     True
+///
+/// Merging dictionaries:
+///
+///     >>> merged, conflicts = rtfcre.merge(base, ours, theirs)
+///     >>> conflicts
+///     [("KAT", ("cat", None), ("cat", "ours"), ("Cat", None))]
+///     >>> with open("conflicts.json", "wb") as file:
+///     ...     merged, conflicts = rtfcre.merge(base, ours, theirs, conflicts=file)
+///     >>> with open("conflicts.json", "rb") as file:
+///     ...     merged.resolve(file)
+///     []
+///
 fn rtfcre(_: Python, m: &PyModule) -> PyResult<()> {
   m.add_function(wrap_pyfunction!(load, m)?)?;
   m.add_function(wrap_pyfunction!(loads, m)?)?;
+  m.add_function(wrap_pyfunction!(merge, m)?)?;
   m.add_class::<RtfDictionary>()?;
+  m.add_class::<Transaction>()?;
 
   Ok(())
 }
@@ -0,0 +1,105 @@
+use crate::dict::Dictionary;
+use crate::rtf::parse_rtf_entries;
+use crate::translation::{format_plover_to_rtf, parse_translation as parse_plover};
+use crate::translation_model::Object;
+use crate::translation_parse::{format_rtf_to_plover, parse_translation as parse_rtf};
+
+/// An entry whose translation does not survive conversion between Plover and
+/// RTF syntax. Each direction is only filled in if it is lossy.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct LossyEntry {
+  pub steno: String,
+  pub translation: String,
+  pub rtf: String,
+  /// The translation after going Plover → RTF → Plover.
+  pub plover_round_trip: Option<String>,
+  /// The RTF after going RTF → Plover → RTF.
+  pub rtf_round_trip: Option<String>,
+}
+
+// Two translations are considered equivalent if they parse to the same
+// sequence of objects, so e.g. `{:stop:.}` and `{.}` are not reported as
+// different. Plover command names are case-insensitive, so `{PLOVER:LOOKUP}`
+// and `{plover:lookup}` are also equivalent.
+fn canonicalize(objects: Vec<Object>) -> Vec<Object> {
+  let mut canonical: Vec<Object> = Vec::with_capacity(objects.len());
+  for obj in objects {
+    match (canonical.last_mut(), obj) {
+      (_, Object::RawString(ref s)) if s.is_empty() => {},
+      (Some(Object::RawString(prev)), Object::RawString(s)) => prev.push_str(&s),
+      (_, Object::Command(name, arg)) => canonical.push(Object::Command(name.to_lowercase(), arg)),
+      (_, obj) => canonical.push(obj),
+    }
+  }
+  canonical
+}
+
+fn plover_equivalent(a: &str, b: &str) -> bool {
+  canonicalize(parse_plover(a.trim())) == canonicalize(parse_plover(b.trim()))
+}
+
+fn rtf_equivalent(a: &str, b: &str) -> bool {
+  canonicalize(parse_rtf(a.trim())) == canonicalize(parse_rtf(b.trim()))
+}
+
+/// Check a single translation, returning the lossy directions if any.
+pub fn check_translation(steno: &str, translation: &str) -> Option<LossyEntry> {
+  let rtf = format_plover_to_rtf(translation);
+  let plover = format_rtf_to_plover(&rtf);
+  let rtf_again = format_plover_to_rtf(&plover);
+
+  let plover_lossy = !plover_equivalent(translation, &plover);
+  let rtf_lossy = !rtf_equivalent(&rtf, &rtf_again);
+  if !plover_lossy && !rtf_lossy {
+    return None;
+  }
+
+  Some(LossyEntry {
+    steno: steno.to_string(),
+    translation: translation.to_string(),
+    rtf,
+    plover_round_trip: if plover_lossy { Some(plover) } else { None },
+    rtf_round_trip: if rtf_lossy { Some(rtf_again) } else { None },
+  })
+}
+
+/// Check a translation as imported from an RTF file, starting from the RTF
+/// as written rather than from RTF generated from Plover syntax, so that
+/// anything lost on import is reported too.
+pub fn check_rtf_translation(steno: &str, rtf: &str) -> Option<LossyEntry> {
+  let translation = format_rtf_to_plover(rtf);
+  let rtf_again = format_plover_to_rtf(&translation);
+  let plover = format_rtf_to_plover(&rtf_again);
+
+  let plover_lossy = !plover_equivalent(&translation, &plover);
+  let rtf_lossy = !rtf_equivalent(rtf, &rtf_again);
+  if !plover_lossy && !rtf_lossy {
+    return None;
+  }
+
+  Some(LossyEntry {
+    steno: steno.to_string(),
+    translation,
+    rtf: rtf.to_string(),
+    plover_round_trip: if plover_lossy { Some(plover) } else { None },
+    rtf_round_trip: if rtf_lossy { Some(rtf_again) } else { None },
+  })
+}
+
+/// Run every entry of the dictionary through both conversions and return
+/// the entries that do not survive, in file order.
+pub fn check_round_trip(dict: &Dictionary) -> Vec<LossyEntry> {
+  dict.entries.values()
+    .filter_map(|entry| check_translation(&entry.steno, &entry.translation))
+    .collect()
+}
+
+/// Run every entry of the RTF dictionary `input` through both conversions,
+/// starting from the RTF of each entry as written, and return the entries
+/// that do not survive in file order. Returns None if the RTF cannot be
+/// parsed.
+pub fn check_rtf_round_trip(input: &str) -> Option<Vec<LossyEntry>> {
+  Some(parse_rtf_entries(input)?.iter()
+    .filter_map(|(steno, rtf)| check_rtf_translation(steno, rtf))
+    .collect())
+}
@@ -6,10 +6,15 @@ use nom::combinator::{opt, recognize};
 use nom::multi::{many0, many1, many_till};
 use nom::sequence::tuple;
 
-use crate::dict::Dictionary;
+use crate::dialect::{ImportProfile, format_rtf_to_plover_with_profile};
+use crate::dict::{Dictionary, Entry, MergedOutline};
+use crate::system::{NumberKey, StenoSystem};
 use crate::translation_parse::format_rtf_to_plover;
 
+use std::borrow::Cow;
 use std::char::from_u32;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 fn unsigned(input: &str) -> IResult<&str, u32> {
   let (input, num) = digit1(input)?;
@@ -60,6 +65,9 @@ fn steno_group(input: &str) -> IResult<&str, String> {
   Ok((input, steno.join("")))
 }
 
+// An entry's outline, translation and comment as written in the file
+type RawEntry = (String, String, Option<String>);
+
 #[derive(Debug)]
 enum TranslationItem {
   Comment(String),
@@ -80,7 +88,7 @@ fn non_comment(input: &str) -> IResult<&str, TranslationItem> {
   Ok((input, TranslationItem::NotComment(item)))
 }
 
-fn steno_entry(input: &str) -> IResult<&str, (String, String, Option<String>)> {
+fn steno_entry(input: &str) -> IResult<&str, RawEntry> {
   let (input, (steno_group, (contents, _))) = tuple((
     steno_group,
     many_till(
@@ -95,7 +103,7 @@ fn steno_entry(input: &str) -> IResult<&str, (String, String, Option<String>)> {
   Ok((input, (steno_group, translation, comment)))
 }
 
-fn last_steno_entry(input: &str) -> IResult<&str, (String, String, Option<String>)> {
+fn last_steno_entry(input: &str) -> IResult<&str, RawEntry> {
   let (input, (steno_group, (contents, _))) = tuple((
     steno_group,
     many_till(
@@ -118,12 +126,12 @@ fn cxsystem(input: &str) -> IResult<&str, String> {
   Ok((input, system.join("").trim().to_string()))
 }
 
-fn no_entries(input: &str) -> IResult<&str, Vec<(String, String, Option<String>)>> {
+fn no_entries(input: &str) -> IResult<&str, Vec<RawEntry>> {
   let (input, _) = recognize(many_till(alt((group, unicode, control_word, control_symbol, text)), tag(r"}")))(input)?;
   Ok((input, vec![]))
 }
 
-fn some_entries(input: &str) -> IResult<&str, Vec<(String, String, Option<String>)>> {
+fn some_entries(input: &str) -> IResult<&str, Vec<RawEntry>> {
   let (input, (_, mut entries, last_entry)) = tuple((
             recognize(many_till(alt((group, unicode, control_word, control_symbol, text)), tag(r"{\*\cxs "))),
     many0(steno_entry),
@@ -133,7 +141,7 @@ fn some_entries(input: &str) -> IResult<&str, Vec<(String, String, Option<String
   Ok((input, entries))
 }
 
-pub fn parse_file(input: &str) -> IResult<&str, Dictionary> {
+fn file_entries(input: &str) -> IResult<&str, (String, Vec<RawEntry>)> {
   let (input, (_, cxsystem, _, entries)) = tuple((
     recognize(tuple((
       multispace0,
@@ -151,15 +159,164 @@ pub fn parse_file(input: &str) -> IResult<&str, Dictionary> {
       some_entries,
       no_entries)),
     ))(input)?;
+  Ok((input, (cxsystem, entries)))
+}
+
+/// The outline and translation of each entry of an RTF dictionary as
+/// written in the file, with translations left in RTF syntax, in file order.
+/// Returns None if the RTF cannot be parsed.
+pub fn parse_rtf_entries(input: &str) -> Option<Vec<(String, String)>> {
+  match file_entries(input.trim()) {
+    Ok((_, (_, entries))) => Some(entries.into_iter()
+      .map(|(steno, translation, _)| (steno, translation.trim().to_string()))
+      .collect()),
+    Err(_) => None,
+  }
+}
+
+/// A control word in an entry that the import profile could not map.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct UnmappedControl {
+  pub steno: String,
+  pub control: String,
+}
+
+/// How outlines are read from a dictionary file.
+#[derive(Clone, Debug)]
+pub struct ParseOptions {
+  /// The steno system the outlines are written in.
+  pub steno_system: Arc<StenoSystem>,
+  /// How to rewrite strokes that use the number key. Defaults to keeping
+  /// them as written.
+  pub number_key: NumberKey,
+  /// Whether to rewrite outlines in canonical form, merging entries whose
+  /// outlines are the same once rewritten.
+  pub normalize_outlines: bool,
+}
 
-  let mut dict = Dictionary::new(&cxsystem);
-  for (mut steno, translation, comment) in entries {
-    if steno.contains("#") && steno.chars().any(|c| matches!(c, '0'..='9')) {
-      steno = steno.replace("#", "")
+impl Default for ParseOptions {
+  fn default() -> Self {
+    Self {
+      steno_system: StenoSystem::builtin("english").unwrap(),
+      number_key: NumberKey::Keep,
+      normalize_outlines: false,
     }
-    dict.add_entry(steno, format_rtf_to_plover(translation.trim()), comment);
   }
-  Ok((input, dict))
+}
+
+impl ParseOptions {
+  /// An outline as it was written in a file, with the strokes that use the
+  /// number key rewritten as `number_key` says.
+  pub fn read_outline<'a>(&self, steno: &'a str) -> Cow<'a, str> {
+    self.steno_system.write_numbers(steno, self.number_key)
+  }
+
+  /// Apply the options to a dictionary that has just been read, returning
+  /// the entries merged by normalizing its outlines. Entries should already
+  /// have been added with an `EntryReader`.
+  pub fn apply(&self, dict: &mut Dictionary) -> Vec<MergedOutline> {
+    dict.steno_system = self.steno_system.clone();
+    match self.normalize_outlines {
+      true => dict.normalize_outlines(),
+      false => vec![],
+    }
+  }
+}
+
+/// Adds the entries of a dictionary file to a dictionary as they are read,
+/// rewriting their outlines with `ParseOptions::read_outline`. Entries whose
+/// outlines became the same because one of them was rewritten are merged, with
+/// the later one replacing the earlier one, and reported.
+pub struct EntryReader<'a> {
+  options: &'a ParseOptions,
+  dict: Dictionary,
+  // The outlines of the entries added so far that were rewritten, mapped to
+  // the outlines they were written as
+  rewritten: HashMap<String, String>,
+  merged: Vec<MergedOutline>,
+}
+
+impl<'a> EntryReader<'a> {
+  pub fn new(options: &'a ParseOptions, dict: Dictionary) -> Self {
+    Self { options, dict, rewritten: HashMap::new(), merged: vec![] }
+  }
+
+  pub fn add_entry(&mut self, written: String, translation: String, comment: Option<String>) {
+    let steno = self.options.read_outline(&written).into_owned();
+    let was_rewritten = steno != written;
+    if was_rewritten || self.rewritten.contains_key(&steno) {
+      if let Some(replaced) = self.dict.entry(&steno) {
+        let replaced_steno = self.rewritten.get(&steno).unwrap_or(&steno);
+        self.merged.push(MergedOutline {
+          steno: steno.clone(),
+          replaced: Entry::new(replaced_steno, &replaced.translation, replaced.comment().as_deref()),
+          kept: Entry::new(&written, &translation, comment.as_deref()),
+        });
+      }
+    }
+    match was_rewritten {
+      true => self.rewritten.insert(steno.clone(), written),
+      false => self.rewritten.remove(&steno),
+    };
+    self.dict.add_entry(steno, translation, comment);
+  }
+
+  /// The dictionary with the options applied, and every entry merged while
+  /// reading and applying them.
+  pub fn finish(mut self) -> (Dictionary, Vec<MergedOutline>) {
+    let mut merged = self.merged;
+    merged.extend(self.options.apply(&mut self.dict));
+    (self.dict, merged)
+  }
+}
+
+/// A dictionary read with `parse_file_with_options`.
+#[derive(Debug)]
+pub struct ParsedDictionary {
+  pub dict: Dictionary,
+  /// The controls the import profile could not map, if one was given.
+  pub unmapped: Vec<UnmappedControl>,
+  /// The entries merged by rewriting number keys or normalizing outlines.
+  pub merged: Vec<MergedOutline>,
+}
+
+/// Parse a dictionary, reading its outlines with `options`. If `profile` is
+/// given, the dictionary is taken to be exported from another CAT system and
+/// its vendor-specific controls are mapped with it.
+pub fn parse_file_with_options<'a>(input: &'a str, options: &ParseOptions,
+    profile: Option<&ImportProfile>) -> IResult<&'a str, ParsedDictionary> {
+  let (input, (cxsystem, entries)) = file_entries(input)?;
+
+  let mut reader = EntryReader::new(options, Dictionary::new(&cxsystem));
+  let mut unmapped = vec![];
+  for (steno, translation, comment) in entries {
+    let translation = match profile {
+      Some(profile) => {
+        let (translation, controls) = format_rtf_to_plover_with_profile(translation.trim(), profile);
+        for control in controls {
+          unmapped.push(UnmappedControl { steno: steno.clone(), control });
+        }
+        translation
+      },
+      None => format_rtf_to_plover(translation.trim()),
+    };
+    reader.add_entry(steno, translation, comment);
+  }
+  let (dict, merged) = reader.finish();
+  Ok((input, ParsedDictionary { dict, unmapped, merged }))
+}
+
+pub fn parse_file(input: &str) -> IResult<&str, Dictionary> {
+  let (input, parsed) = parse_file_with_options(input, &ParseOptions::default(), None)?;
+  Ok((input, parsed.dict))
+}
+
+/// Parse a dictionary exported from another CAT system, mapping its
+/// vendor-specific controls with `profile`.
+pub fn parse_file_with_profile<'a>(input: &'a str, profile: &ImportProfile)
+    -> IResult<&'a str, (Dictionary, Vec<UnmappedControl>)> {
+  let (input, parsed) = parse_file_with_options(input, &ParseOptions::default(), Some(profile))?;
+  Ok((input, (parsed.dict, parsed.unmapped)))
 }
 
 pub fn parse_rtf(input: &str) -> Option<Dictionary> {
@@ -168,3 +325,14 @@ pub fn parse_rtf(input: &str) -> Option<Dictionary> {
     Err(_) => None,
   }
 }
+
+/// Parse an RTF dictionary like `parse_rtf`, reading its outlines with
+/// `options`. Also returns the entries merged by rewriting number keys or
+/// normalizing outlines.
+pub fn parse_rtf_with_options(input: &str, options: &ParseOptions)
+    -> Option<(Dictionary, Vec<MergedOutline>)> {
+  match parse_file_with_options(input.trim(), options, None) {
+    Ok((_, parsed)) => Some((parsed.dict, parsed.merged)),
+    Err(_) => None,
+  }
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::dict::Entry;
+use crate::error::Error;
+
+/// The part of an entry a search pattern is matched against.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SearchField {
+  Outline,
+  Translation,
+  Comment,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PatternKind {
+  Substring,
+  Glob,
+  Regex,
+}
+
+#[derive(Debug)]
+pub struct SearchParseError {
+  value: String,
+}
+
+impl fmt::Display for SearchParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "unknown search option {:?}", self.value)
+  }
+}
+
+impl FromStr for SearchField {
+  type Err = SearchParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "outline" | "steno" => Ok(Self::Outline),
+      "translation" => Ok(Self::Translation),
+      "comment" => Ok(Self::Comment),
+      _ => Err(SearchParseError { value: s.to_string() }),
+    }
+  }
+}
+
+impl FromStr for PatternKind {
+  type Err = SearchParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "substring" => Ok(Self::Substring),
+      "glob" => Ok(Self::Glob),
+      "regex" => Ok(Self::Regex),
+      _ => Err(SearchParseError { value: s.to_string() }),
+    }
+  }
+}
+
+/// A compiled search pattern. Globs must match the whole text; `*` and `?`
+/// do not match `/`, so `*/-G` finds two-stroke outlines ending in -G. Use
+/// `\*` to match the asterisk key itself.
+#[derive(Clone, Debug)]
+pub enum Pattern {
+  Substring(String),
+  Regex(Regex),
+}
+
+fn glob_to_regex(glob: &str) -> Result<String, String> {
+  let mut re = String::from("^");
+  let mut chars = glob.chars();
+  while let Some(c) = chars.next() {
+    match c {
+      '*' => re.push_str("[^/]*"),
+      '?' => re.push_str("[^/]"),
+      '\\' => match chars.next() {
+        Some(escaped) => re.push_str(&regex::escape(&escaped.to_string())),
+        None => return Err("trailing backslash".to_string()),
+      },
+      '[' => {
+        re.push('[');
+        let mut first = true;
+        loop {
+          match chars.next() {
+            Some('!') if first => re.push('^'),
+            Some(']') if !first => break,
+            Some(c) if c == '\\' || c == '[' || c == ']' || c == '^' => {
+              re.push('\\');
+              re.push(c);
+            },
+            Some(c) => re.push(c),
+            None => return Err("unterminated character class".to_string()),
+          }
+          first = false;
+        }
+        re.push(']');
+      },
+      c => re.push_str(&regex::escape(&c.to_string())),
+    }
+  }
+  re.push('$');
+  Ok(re)
+}
+
+impl Pattern {
+  pub fn new(kind: PatternKind, pattern: &str) -> Result<Self, Error> {
+    let invalid = |reason: String| Error::InvalidPattern {
+      pattern: pattern.to_string(),
+      reason,
+    };
+
+    let re = match kind {
+      PatternKind::Substring => return Ok(Self::Substring(pattern.to_string())),
+      PatternKind::Glob => glob_to_regex(pattern).map_err(invalid)?,
+      PatternKind::Regex => pattern.to_string(),
+    };
+    match Regex::new(&re) {
+      Ok(re) => Ok(Self::Regex(re)),
+      Err(err) => Err(invalid(err.to_string())),
+    }
+  }
+
+  pub fn is_match(&self, text: &str) -> bool {
+    match self {
+      Self::Substring(substring) => text.contains(substring.as_str()),
+      Self::Regex(re) => re.is_match(text),
+    }
+  }
+
+  pub fn matches_entry(&self, entry: &Entry, field: SearchField) -> bool {
+    match field {
+      SearchField::Outline => self.is_match(&entry.steno),
+      SearchField::Translation => self.is_match(&entry.translation),
+      SearchField::Comment => entry.comment().map_or(false, |comment| self.is_match(&comment)),
+    }
+  }
+}
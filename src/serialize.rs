@@ -0,0 +1,61 @@
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::dict::{Dictionary, Entry};
+use crate::system::StenoSystem;
+
+// Entries are written as a sequence rather than a map so that their order
+// survives formats that don't preserve the order of map keys.
+struct Entries<'a>(&'a Dictionary);
+
+impl Serialize for Entries<'_> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(self.0.entries.values())
+  }
+}
+
+impl Serialize for Dictionary {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct("Dictionary", 4)?;
+    state.serialize_field("cre_system", &self.cre_system)?;
+    state.serialize_field("steno_system", self.steno_system.id())?;
+    state.serialize_field("normalize", &self.normalizes_outlines())?;
+    state.serialize_field("entries", &Entries(self))?;
+    state.end()
+  }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "Dictionary")]
+struct DictionaryData {
+  cre_system: String,
+  /// English stenotype if not given.
+  #[serde(default)]
+  steno_system: Option<String>,
+  #[serde(default)]
+  normalize: bool,
+  #[serde(default)]
+  entries: Vec<Entry>,
+}
+
+impl<'de> Deserialize<'de> for Dictionary {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let data = DictionaryData::deserialize(deserializer)?;
+    // Build the dictionary entry by entry so the reverse lookup indexes and
+    // longest key are derived the same way as when parsing RTF.
+    let mut dict = Dictionary::new(&data.cre_system);
+    if let Some(system) = data.steno_system {
+      dict.steno_system = StenoSystem::find(&system).map_err(de::Error::custom)?;
+    }
+    for entry in data.entries {
+      let comment = entry.comment();
+      dict.add_entry(entry.steno.to_string(), entry.translation.to_string(), comment);
+    }
+    // The outlines were written normalized, so this only marks the dictionary
+    // to keep normalizing them
+    if data.normalize {
+      dict.normalize_outlines();
+    }
+    Ok(dict)
+  }
+}
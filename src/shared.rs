@@ -0,0 +1,81 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::dict::Dictionary;
+
+/// A dictionary that can be read from many threads while another edits it.
+///
+/// Readers take snapshots, which are never affected by later edits. Each edit
+/// is applied to the dictionary as a whole before readers can see it, so
+/// `entries`, the reverse lookup indexes and `longest_key` always agree with
+/// each other. If no reader is holding a snapshot, the dictionary is edited in
+/// place and readers wait for the edit. Otherwise the edit is made to a copy,
+/// outside the lock, so readers are only held up while it is swapped in.
+pub struct SharedDictionary {
+  current: RwLock<Arc<Dictionary>>,
+  // Held for the whole of an edit, so that editors don't overwrite each
+  // other's copies
+  writer: Mutex<()>,
+}
+
+impl SharedDictionary {
+  pub fn new(dict: Dictionary) -> Self {
+    Self { current: RwLock::new(Arc::new(dict)), writer: Mutex::new(()) }
+  }
+
+  /// The dictionary as of the last completed edit.
+  pub fn snapshot(&self) -> Arc<Dictionary> {
+    self.current.read().expect("shared dictionary lock poisoned").clone()
+  }
+
+  /// Replace the whole dictionary, for example after reloading it.
+  pub fn replace(&self, dict: Dictionary) {
+    let _writer = self.writer.lock().expect("shared dictionary lock poisoned");
+    self.publish(dict);
+  }
+
+  fn publish(&self, dict: Dictionary) {
+    *self.current.write().expect("shared dictionary lock poisoned") = Arc::new(dict);
+  }
+
+  /// Edit the dictionary. Readers see none of the edit until it is done, and
+  /// other editors wait their turn.
+  pub fn write<T>(&self, edit: impl FnOnce(&mut Dictionary) -> T) -> T {
+    let _writer = self.writer.lock().expect("shared dictionary lock poisoned");
+    let mut current = self.current.write().expect("shared dictionary lock poisoned");
+    if let Some(dict) = Arc::get_mut(&mut current) {
+      return edit(dict);
+    }
+
+    // Readers are holding snapshots of the dictionary, so edit a copy
+    let snapshot = current.clone();
+    drop(current);
+    let mut dict = Dictionary::clone(&snapshot);
+    drop(snapshot);
+    let result = edit(&mut dict);
+    self.publish(dict);
+    result
+  }
+
+  /// Edit the dictionary, rolling back the changes if `edit` fails so readers
+  /// never see any of them. Nothing is kept to undo.
+  pub fn try_write<T, E>(&self, edit: impl FnOnce(&mut Dictionary) -> Result<T, E>)
+      -> Result<T, E> {
+    self.write(|dict| dict.atomically(edit))
+  }
+
+  pub fn contains_key(&self, steno: &str) -> bool {
+    self.snapshot().contains_key(steno)
+  }
+
+  pub fn lookup(&self, steno: &str) -> Option<String> {
+    self.snapshot().lookup(steno)
+  }
+
+  pub fn rev_lookup(&self, translation: &str) -> Option<Vec<String>> {
+    self.snapshot().rev_lookup(translation)
+  }
+
+  pub fn longest_key(&self) -> usize {
+    self.snapshot().longest_key
+  }
+}
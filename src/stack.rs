@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use crate::dict::{Dictionary, Entry};
+
+/// An entry found in a dictionary stack, along with the name of the
+/// dictionary it came from.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StackEntry<'a> {
+  pub dictionary: &'a str,
+  pub entry: &'a Entry,
+}
+
+/// An outline defined in more than one dictionary in a stack. Only the
+/// definition in the highest-priority dictionary takes effect.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ShadowedEntry<'a> {
+  pub steno: &'a str,
+  pub active: StackEntry<'a>,
+  /// The definitions hidden by the active one, highest priority first.
+  pub shadowed: Vec<StackEntry<'a>>,
+}
+
+/// Several dictionaries layered by priority, as in Plover's dictionary stack.
+/// Dictionaries pushed later sit on top of earlier ones and take priority
+/// over them.
+#[derive(Default)]
+pub struct DictionaryStack {
+  // Lowest priority first
+  dicts: Vec<(String, Dictionary)>,
+}
+
+impl DictionaryStack {
+  pub fn new() -> Self {
+    Self { dicts: vec![] }
+  }
+
+  /// Add a dictionary on top of the stack, giving it the highest priority.
+  pub fn push(&mut self, name: &str, dict: Dictionary) {
+    self.dicts.push((name.to_string(), dict));
+  }
+
+  /// Remove a dictionary from the stack and return it.
+  pub fn remove(&mut self, name: &str) -> Option<Dictionary> {
+    let index = self.dicts.iter().position(|(n, _)| n == name)?;
+    Some(self.dicts.remove(index).1)
+  }
+
+  pub fn len(&self) -> usize {
+    self.dicts.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.dicts.is_empty()
+  }
+
+  pub fn get(&self, name: &str) -> Option<&Dictionary> {
+    self.dicts.iter().find(|(n, _)| n == name).map(|(_, dict)| dict)
+  }
+
+  pub fn get_mut(&mut self, name: &str) -> Option<&mut Dictionary> {
+    self.dicts.iter_mut().find(|(n, _)| n == name).map(|(_, dict)| dict)
+  }
+
+  /// The names and dictionaries in the stack, highest priority first.
+  pub fn dictionaries(&self) -> impl Iterator<Item = (&str, &Dictionary)> {
+    self.dicts.iter().rev().map(|(name, dict)| (name.as_str(), dict))
+  }
+
+  pub fn longest_key(&self) -> usize {
+    self.dicts.iter().map(|(_, dict)| dict.longest_key).max().unwrap_or(0)
+  }
+
+  /// The entry for `steno` in the highest-priority dictionary defining it.
+  pub fn lookup(&self, steno: &str) -> Option<StackEntry<'_>> {
+    self.dictionaries().find_map(|(dictionary, dict)| {
+      Some(StackEntry { dictionary, entry: dict.entry(steno)? })
+    })
+  }
+
+  /// The entries translating to `translation`, leaving out any whose outline
+  /// is overridden by a higher-priority dictionary.
+  pub fn rev_lookup(&self, translation: &str) -> Vec<StackEntry<'_>> {
+    let mut entries = vec![];
+    let mut seen = HashSet::new();
+    // Dictionaries are told apart by position, since names need not be
+    // unique
+    let dicts: Vec<(&str, &Dictionary)> = self.dictionaries().collect();
+    for (i, (dictionary, dict)) in dicts.iter().enumerate() {
+      let mut outlines = dict.rev_lookup(translation).unwrap_or_default();
+      outlines.sort();
+      for steno in outlines {
+        if seen.contains(&steno) {
+          continue;
+        }
+        let overridden = dicts[..i].iter().any(|(_, higher)| higher.entry(&steno).is_some());
+        if !overridden {
+          if let Some(entry) = dict.entry(&steno) {
+            entries.push(StackEntry { dictionary, entry });
+          }
+        }
+        seen.insert(steno);
+      }
+    }
+    entries
+  }
+
+  /// The outlines defined in more than one dictionary, in order of the
+  /// dictionary that defines them with the highest priority.
+  pub fn shadowed(&self) -> Vec<ShadowedEntry<'_>> {
+    let mut shadowed = vec![];
+    let mut seen = HashSet::new();
+    let dicts: Vec<(&str, &Dictionary)> = self.dictionaries().collect();
+    for (i, (dictionary, dict)) in dicts.iter().enumerate() {
+      for (steno, entry) in &dict.entries {
+        if !seen.insert(&**steno) {
+          continue;
+        }
+        let hidden: Vec<StackEntry> = dicts[i + 1..].iter()
+          .filter_map(|(dictionary, dict)| {
+            Some(StackEntry { dictionary, entry: dict.entry(steno)? })
+          })
+          .collect();
+        if !hidden.is_empty() {
+          shadowed.push(ShadowedEntry {
+            steno,
+            active: StackEntry { dictionary, entry },
+            shadowed: hidden,
+          });
+        }
+      }
+    }
+    shadowed
+  }
+}
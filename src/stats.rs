@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::dict::{Dictionary, Entry};
+use crate::translation::parse_translation;
+use crate::translation_model::Object;
+
+/// The kind of thing an entry writes, judged from its translation and the
+/// number of strokes in its outline.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum EntryKind {
+  /// A one-stroke outline for one or more words.
+  Brief,
+  /// An outline of more than one stroke for one or more words.
+  MultiStroke,
+  /// Letters that are attached to each other, like `{&a}`.
+  Fingerspelling,
+  /// Punctuation only, like `{.}` or `{,}`.
+  Punctuation,
+  /// Prefixes, suffixes and infixes, like `{^ing}` or `{re^}`.
+  Affix,
+  /// Plover commands, key combinations, macros and meta translations.
+  Command,
+  /// Anything else, such as translations that only change formatting.
+  Other,
+}
+
+impl EntryKind {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Brief => "brief",
+      Self::MultiStroke => "multi-stroke",
+      Self::Fingerspelling => "fingerspelling",
+      Self::Punctuation => "punctuation",
+      Self::Affix => "affix",
+      Self::Command => "command",
+      Self::Other => "other",
+    }
+  }
+
+  pub fn of(entry: &Entry) -> Self {
+    let objects = parse_translation(&entry.translation);
+    let is = |matches: fn(&Object) -> bool| objects.iter().any(matches);
+    let text: Vec<&Object> = objects.iter().filter(|obj| obj.text().is_some()).collect();
+
+    if is(|obj| matches!(obj, Object::Command(..) | Object::Meta(..) | Object::Macro(..) |
+        Object::KeyCombo(_) | Object::DeleteStroke | Object::RepeatLastStroke |
+        Object::RetroToggleStar | Object::RetroInsertSpace | Object::RetroDeleteSpace)) {
+      Self::Command
+    } else if text.is_empty() {
+      Self::Other
+    } else if text.iter().all(|obj| matches!(obj, Object::Fingerspell(_))) {
+      Self::Fingerspelling
+    } else if text.iter().all(|obj| obj.text().unwrap().chars()
+        .all(|c| c.is_ascii_punctuation() || c.is_whitespace())) {
+      Self::Punctuation
+    } else if is(|obj| matches!(obj, Object::AttachPrefix(_) | Object::AttachSuffix(_) |
+        Object::AttachInfix(_) | Object::CarryCapPrefix(_) | Object::CarryCapSuffix(_) |
+        Object::CarryCapInfix(_) | Object::OrthoAttach)) {
+      Self::Affix
+    } else if entry.steno.contains('/') {
+      Self::MultiStroke
+    } else {
+      Self::Brief
+    }
+  }
+}
+
+impl fmt::Display for EntryKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+/// Statistics about the entries in a dictionary.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Stats {
+  pub entries: usize,
+  pub longest_key: usize,
+  /// The number of outlines with each number of strokes.
+  pub stroke_counts: BTreeMap<usize, usize>,
+  /// The number of entries of each kind, leaving out kinds with none.
+  pub kinds: BTreeMap<EntryKind, usize>,
+  /// The number of entries with a comment.
+  pub commented: usize,
+  /// The translations with the most outlines and how many each has, most
+  /// first.
+  pub most_outlines: Vec<(String, usize)>,
+}
+
+impl Stats {
+  /// The fraction of entries with a comment, from 0 to 1.
+  pub fn comment_coverage(&self) -> f64 {
+    match self.entries {
+      0 => 0.0,
+      n => self.commented as f64 / n as f64,
+    }
+  }
+}
+
+/// Gather statistics about `dict`, listing up to `top` of the translations
+/// with more than one outline. Translations with the same number of outlines
+/// are listed alphabetically.
+pub fn stats(dict: &Dictionary, top: usize) -> Stats {
+  let mut kinds = BTreeMap::new();
+  let mut commented = 0;
+  for entry in dict.entries.values() {
+    *kinds.entry(EntryKind::of(entry)).or_default() += 1;
+    if entry.comment().is_some() {
+      commented += 1;
+    }
+  }
+
+  let mut most_outlines: Vec<(String, usize)> = dict.reverse_entries.iter()
+    .filter(|(_, outlines)| outlines.len() > 1)
+    .map(|(translation, outlines)| (translation.to_string(), outlines.len()))
+    .collect();
+  most_outlines.sort_by(|(tl1, n1), (tl2, n2)| n2.cmp(n1).then_with(|| tl1.cmp(tl2)));
+  most_outlines.truncate(top);
+
+  Stats {
+    entries: dict.len(),
+    longest_key: dict.longest_key,
+    stroke_counts: dict.stroke_counts().clone(),
+    kinds,
+    commented,
+    most_outlines,
+  }
+}
@@ -0,0 +1,99 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::system::StenoSystem;
+
+/// A single steno stroke, as the set of keys pressed. Which keys those are
+/// depends on the steno system the stroke was parsed with; the methods that
+/// take or return key names use English stenotype, and `StenoSystem` has
+/// equivalents for other systems.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub struct Stroke {
+  keys: u64,
+}
+
+impl Stroke {
+  /// Parse an English stenotype stroke written the way Plover writes it,
+  /// such as `KAT`, `KA*T`, `-T`, `1-9` or `#STKP`. Keys must be in steno
+  /// order, and a hyphen may only separate the two banks.
+  pub fn parse(stroke: &str) -> Result<Self, Error> {
+    StenoSystem::english().parse_stroke(stroke)
+  }
+
+  pub(crate) fn has(&self, position: usize) -> bool {
+    self.keys & (1 << position) != 0
+  }
+
+  pub(crate) fn with(&self, position: usize) -> Self {
+    Self { keys: self.keys | (1 << position) }
+  }
+
+  pub(crate) fn without(&self, position: usize) -> Self {
+    Self { keys: self.keys & !(1 << position) }
+  }
+
+  /// The position of each key of the stroke in steno order.
+  pub fn positions(&self) -> Vec<usize> {
+    (0..64).filter(|i| self.has(*i)).collect()
+  }
+
+  /// The keys in the stroke in steno order, named as in Plover, such as
+  /// `S-`, `*` or `-T`.
+  pub fn keys(&self) -> Vec<&'static str> {
+    StenoSystem::english().stroke_keys(self)
+  }
+
+  pub fn contains(&self, key: &str) -> bool {
+    StenoSystem::english().contains_key(self, key)
+  }
+
+  /// The stroke with `key` pressed if it was not, or released if it was.
+  /// Names that are not keys are ignored.
+  pub fn toggle(&self, key: &str) -> Self {
+    StenoSystem::english().toggle_key(self, key)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.keys == 0
+  }
+}
+
+impl FromStr for Stroke {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::parse(s)
+  }
+}
+
+/// Writes the stroke in canonical English stenotype form.
+impl fmt::Display for Stroke {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", StenoSystem::english().format_stroke(self))
+  }
+}
+
+/// Strokes compare in steno order, key by key.
+impl Ord for Stroke {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.positions().cmp(&other.positions())
+  }
+}
+
+impl PartialOrd for Stroke {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+/// Parse each stroke of an English stenotype outline such as `KAT/-S`.
+pub fn parse_outline(steno: &str) -> Result<Vec<Stroke>, Error> {
+  StenoSystem::english().parse_outline(steno)
+}
+
+/// Write an English stenotype outline with each stroke in canonical form.
+pub fn format_outline(strokes: &[Stroke]) -> String {
+  StenoSystem::english().format_outline(strokes)
+}
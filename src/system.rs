@@ -0,0 +1,418 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::iter;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::stroke::Stroke;
+
+lazy_static! {
+  static ref ENGLISH: Arc<StenoSystem> = Arc::new(StenoSystem::new(
+    "English Stenotype",
+    &["#", "S-", "T-", "K-", "P-", "W-", "H-", "R-", "A-", "O-",
+      "*", "-E", "-U", "-F", "-R", "-P", "-B", "-L", "-G", "-T", "-S", "-D", "-Z"],
+    &["A-", "O-", "*", "-E", "-U"],
+    Some("#"),
+    &[("S-", '1'), ("T-", '2'), ("P-", '3'), ("H-", '4'), ("A-", '5'),
+      ("O-", '0'), ("-F", '6'), ("-P", '7'), ("-L", '8'), ("-T", '9')],
+  ).unwrap().with_id("english"));
+
+  static ref PALANTYPE: Arc<StenoSystem> = Arc::new(StenoSystem::new(
+    "Palantype",
+    &["S-", "C-", "P-", "T-", "H-", "+-", "M-", "F-", "R-", "N-", "L-", "Y-",
+      "O-", "E-", "A-", "U", "-I",
+      "-^", "-N", "-L", "-C", "-M", "-F", "-R", "-P", "-T", "-+", "-S", "-H"],
+    &["O-", "E-", "A-", "U", "-I"],
+    None,
+    &[],
+  ).unwrap().with_id("palantype"));
+}
+
+/// How strokes that use the number key are written.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum NumberKey {
+  /// As they were written, with digits, the number key or both.
+  #[default]
+  Keep,
+  /// With digits in place of the number key where possible, as Plover
+  /// writes them, such as `1-9`.
+  Digits,
+  /// With the number key and the letters of the keys, such as `#S-T`.
+  Explicit,
+  /// Without the number key in outlines that also have digits, so `#1-9` is
+  /// written `1-9`, as RTF dictionaries used to be read. Other outlines are
+  /// left as they were written.
+  Strip,
+}
+
+#[derive(Debug)]
+pub struct NumberKeyParseError {
+  value: String,
+}
+
+impl fmt::Display for NumberKeyParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "unknown number key handling {:?}", self.value)
+  }
+}
+
+impl FromStr for NumberKey {
+  type Err = NumberKeyParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "keep" => Ok(Self::Keep),
+      "digits" => Ok(Self::Digits),
+      "explicit" => Ok(Self::Explicit),
+      "strip" => Ok(Self::Strip),
+      _ => Err(NumberKeyParseError { value: s.to_string() }),
+    }
+  }
+}
+
+// The character a key is written with, without the hyphen that shows which
+// bank it is on
+fn letter(key: &str) -> &str {
+  key.trim_matches('-')
+}
+
+/// A steno layout: its keys in steno order, the keys that separate the two
+/// banks so that strokes using them need no hyphen, and the number key and
+/// the digit each key writes when pressed with it. Keys are named as in
+/// Plover, with a trailing hyphen for keys on the left bank and a leading one
+/// for keys on the right.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StenoSystem {
+  id: String,
+  name: String,
+  keys: Vec<String>,
+  implicit_hyphen: Vec<usize>,
+  right_bank: usize,
+  number_key: Option<usize>,
+  numbers: Vec<(char, usize)>,
+}
+
+impl StenoSystem {
+  /// Define a steno system. `numbers` maps keys to the digits they write
+  /// with the number key.
+  pub fn new(name: &str, keys: &[&str], implicit_hyphen_keys: &[&str],
+      number_key: Option<&str>, numbers: &[(&str, char)]) -> Result<Self, Error> {
+    let invalid = |reason: String| Error::InvalidStenoSystem { reason };
+    if keys.is_empty() || keys.len() > 64 {
+      return Err(invalid("must have between 1 and 64 keys".to_string()));
+    }
+    let mut seen = HashSet::new();
+    for key in keys {
+      if !seen.insert(key) {
+        return Err(invalid(format!("key {:?} is defined more than once", key)));
+      }
+      let mut chars = letter(key).chars();
+      match (chars.next(), chars.next()) {
+        (Some(c), None) if c != '/' && !c.is_ascii_digit() => {},
+        _ => return Err(invalid(format!("key {:?} must be a single character", key))),
+      }
+    }
+
+    let position = |key: &str| keys.iter().position(|k| *k == key)
+      .ok_or_else(|| invalid(format!("unknown key {:?}", key)));
+    let implicit_hyphen = implicit_hyphen_keys.iter()
+      .map(|key| position(key))
+      .collect::<Result<Vec<_>, _>>()?;
+    let number_key = number_key.map(&position).transpose()?;
+    let numbers = numbers.iter()
+      .map(|(key, digit)| match digit.is_ascii_digit() {
+        true => Ok((*digit, position(key)?)),
+        false => Err(invalid(format!("{:?} is not a digit", digit))),
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    if number_key.is_none() && !numbers.is_empty() {
+      return Err(invalid("numbers require a number key".to_string()));
+    }
+
+    Ok(Self {
+      id: name.to_string(),
+      name: name.to_string(),
+      keys: keys.iter().map(|key| key.to_string()).collect(),
+      implicit_hyphen,
+      right_bank: keys.iter().position(|key| key.starts_with('-')).unwrap_or(keys.len()),
+      number_key,
+      numbers,
+    })
+  }
+
+  /// Read a steno system from JSON, in the form
+  /// `{"name": ..., "keys": [...], "implicit_hyphen_keys": [...],
+  /// "number_key": ..., "numbers": {key: digit, ...}}`. The number key and
+  /// numbers may be left out for systems without them.
+  pub fn from_json(json: &str) -> Result<Self, Error> {
+    let invalid = |reason: String| Error::InvalidStenoSystem { reason };
+    let value: Value = serde_json::from_str(json).map_err(|err| invalid(err.to_string()))?;
+    let map = value.as_object().ok_or_else(|| invalid("expected an object".to_string()))?;
+    let strings = |key: &str| match map.get(key) {
+      Some(Value::Array(values)) => values.iter()
+        .map(|value| value.as_str()
+          .ok_or_else(|| invalid(format!("expected a list of strings for {:?}", key))))
+        .collect::<Result<Vec<&str>, _>>(),
+      _ => Err(invalid(format!("expected a list of strings for {:?}", key))),
+    };
+
+    let name = match map.get("name") {
+      Some(Value::String(name)) => name.as_str(),
+      _ => return Err(invalid("expected a string for \"name\"".to_string())),
+    };
+    let number_key = match map.get("number_key") {
+      Some(Value::String(key)) => Some(key.as_str()),
+      Some(Value::Null) | None => None,
+      _ => return Err(invalid("expected a string or null for \"number_key\"".to_string())),
+    };
+    let numbers = match map.get("numbers") {
+      Some(Value::Object(numbers)) => numbers.iter()
+        .map(|(key, digit)| {
+          let mut chars = digit.as_str().unwrap_or_default().chars();
+          match (chars.next(), chars.next()) {
+            (Some(digit), None) => Ok((key.as_str(), digit)),
+            _ => Err(invalid(format!("expected a digit for key {:?}", key))),
+          }
+        })
+        .collect::<Result<Vec<_>, _>>()?,
+      Some(Value::Null) | None => vec![],
+      _ => return Err(invalid("expected an object for \"numbers\"".to_string())),
+    };
+    Self::new(name, &strings("keys")?, &strings("implicit_hyphen_keys")?, number_key, &numbers)
+  }
+
+  /// Read a steno system from a JSON file, as in `from_json`.
+  pub fn load(path: &Path) -> Result<Self, Error> {
+    let json = fs::read_to_string(path)
+      .map_err(|err| Error::InvalidStenoSystem { reason: err.to_string() })?;
+    Self::from_json(&json).map(|system| system.with_id(&path.to_string_lossy()))
+  }
+
+  fn with_id(self, id: &str) -> Self {
+    Self { id: id.to_string(), ..self }
+  }
+
+  /// English stenotype, as used by Plover.
+  pub fn english() -> &'static Self {
+    &ENGLISH
+  }
+
+  /// Palantype, as used by the Plover Palantype plugin.
+  pub fn palantype() -> &'static Self {
+    &PALANTYPE
+  }
+
+  /// The built-in system with the given name, either "english" or
+  /// "palantype".
+  pub fn builtin(name: &str) -> Option<Arc<Self>> {
+    match name {
+      "english" => Some(ENGLISH.clone()),
+      "palantype" => Some(PALANTYPE.clone()),
+      _ => None,
+    }
+  }
+
+  /// The built-in system named `system`, or else the system defined in the
+  /// JSON file at that path.
+  pub fn find(system: &str) -> Result<Arc<Self>, Error> {
+    match Self::builtin(system) {
+      Some(system) => Ok(system),
+      None => Self::load(Path::new(system)).map(Arc::new),
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// What `find` finds this system by: "english" or "palantype" for the
+  /// built-in systems, or the path of the file it was loaded from. Systems
+  /// defined in code go by their name.
+  pub fn id(&self) -> &str {
+    &self.id
+  }
+
+  /// The names of the keys in steno order.
+  pub fn keys(&self) -> Vec<&str> {
+    self.keys.iter().map(|key| key.as_str()).collect()
+  }
+
+  fn position(&self, key: &str) -> Option<usize> {
+    self.keys.iter().position(|k| k == key)
+  }
+
+  fn digit(&self, position: usize) -> Option<char> {
+    self.numbers.iter().find(|(_, key)| *key == position).map(|(digit, _)| *digit)
+  }
+
+  /// Parse a stroke written the way Plover writes it. Keys must be in steno
+  /// order, and a hyphen may only separate the two banks.
+  pub fn parse_stroke(&self, stroke: &str) -> Result<Stroke, Error> {
+    let invalid = |reason: String| Error::InvalidStroke {
+      stroke: stroke.to_string(),
+      reason,
+    };
+    if stroke.is_empty() {
+      return Err(invalid("stroke is empty".to_string()));
+    }
+
+    let is_key = |c: char| self.keys.iter().any(|key| letter(key).starts_with(c));
+    let mut keys = Stroke::default();
+    let mut position = 0;
+    let mut hyphen = false;
+    for c in stroke.chars() {
+      if c == '-' {
+        if hyphen || position > self.right_bank {
+          return Err(invalid("hyphen is out of place".to_string()));
+        }
+        hyphen = true;
+        position = self.right_bank;
+        continue;
+      }
+
+      let key = match self.numbers.iter().find(|(digit, _)| *digit == c) {
+        Some((_, key)) if *key >= position => Some(*key),
+        Some(_) => None,
+        None => (position..self.keys.len()).find(|i| letter(&self.keys[*i]).starts_with(c)),
+      };
+      match (key, self.number_key) {
+        (Some(key), Some(number_key)) if c.is_ascii_digit() => {
+          keys = keys.with(number_key).with(key);
+          position = key + 1;
+        },
+        (Some(key), _) => {
+          keys = keys.with(key);
+          position = key + 1;
+        },
+        (None, _) if is_key(c) || self.numbers.iter().any(|(digit, _)| *digit == c) =>
+          return Err(invalid(format!("{:?} is out of steno order", c))),
+        (None, _) => return Err(invalid(format!("{:?} is not a key", c))),
+      }
+    }
+    Ok(keys)
+  }
+
+  /// Write a stroke in canonical form: keys in steno order, a hyphen only
+  /// where it is needed to tell the banks apart, and digits in place of the
+  /// number key where it is pressed with a key that has one.
+  pub fn format_stroke(&self, stroke: &Stroke) -> String {
+    self.write_stroke(stroke, true)
+  }
+
+  fn write_stroke(&self, stroke: &Stroke, digits: bool) -> String {
+    let positions: Vec<usize> = stroke.positions().into_iter()
+      .filter(|i| *i < self.keys.len())
+      .collect();
+    let numbers = digits && self.number_key.map_or(false, |key| stroke.has(key))
+      && positions.iter().any(|i| self.digit(*i).is_some());
+    let needs_hyphen = !positions.iter().any(|i| self.implicit_hyphen.contains(i));
+
+    let mut steno = String::new();
+    for i in positions {
+      if numbers && Some(i) == self.number_key {
+        continue;
+      }
+      if i >= self.right_bank && needs_hyphen && !steno.contains('-') {
+        steno.push('-');
+      }
+      match self.digit(i) {
+        Some(digit) if numbers => steno.push(digit),
+        _ => steno.push_str(letter(&self.keys[i])),
+      }
+    }
+    steno
+  }
+
+  /// The names of the keys in a stroke in steno order.
+  pub fn stroke_keys(&self, stroke: &Stroke) -> Vec<&str> {
+    stroke.positions().into_iter()
+      .filter_map(|i| self.keys.get(i).map(|key| key.as_str()))
+      .collect()
+  }
+
+  pub fn contains_key(&self, stroke: &Stroke, key: &str) -> bool {
+    self.position(key).map_or(false, |i| stroke.has(i))
+  }
+
+  /// The stroke with `key` pressed if it was not, or released if it was.
+  /// Names that are not keys are ignored.
+  pub fn toggle_key(&self, stroke: &Stroke, key: &str) -> Stroke {
+    match self.position(key) {
+      Some(i) if stroke.has(i) => stroke.without(i),
+      Some(i) => stroke.with(i),
+      None => *stroke,
+    }
+  }
+
+  /// Parse each stroke of an outline such as `KAT/-S`.
+  pub fn parse_outline(&self, steno: &str) -> Result<Vec<Stroke>, Error> {
+    steno.split('/').map(|stroke| self.parse_stroke(stroke)).collect()
+  }
+
+  /// Write an outline with each stroke in canonical form.
+  pub fn format_outline(&self, strokes: &[Stroke]) -> String {
+    strokes.iter().map(|stroke| self.format_stroke(stroke)).collect::<Vec<_>>().join("/")
+  }
+
+  // Whether a stroke as written uses the number key, either by name or with
+  // digits
+  fn uses_number_key(&self, stroke: &str) -> bool {
+    let number_key = match self.number_key {
+      Some(key) => letter(&self.keys[key]),
+      None => return false,
+    };
+    stroke.contains(number_key) || stroke.chars().any(|c| self.numbers.iter().any(|(d, _)| *d == c))
+  }
+
+  /// Rewrite the strokes of `steno` that use the number key as `policy`
+  /// says. Other strokes, and strokes that are not valid steno, are left as
+  /// written.
+  pub fn write_numbers<'a>(&self, steno: &'a str, policy: NumberKey) -> Cow<'a, str> {
+    if policy == NumberKey::Keep || !self.uses_number_key(steno) {
+      return Cow::Borrowed(steno);
+    }
+    if policy == NumberKey::Strip {
+      return self.strip_number_key(steno);
+    }
+    Cow::Owned(steno.split('/')
+      .map(|stroke| match self.parse_stroke(stroke) {
+        Ok(parsed) if self.uses_number_key(stroke) =>
+          self.write_stroke(&parsed, policy == NumberKey::Digits),
+        _ => stroke.to_string(),
+      })
+      .collect::<Vec<_>>()
+      .join("/"))
+  }
+
+  // The outline without the number key if it also has digits, whether or not
+  // it is valid steno
+  fn strip_number_key<'a>(&self, steno: &'a str) -> Cow<'a, str> {
+    let number_key = match self.number_key {
+      Some(key) => letter(&self.keys[key]),
+      None => return Cow::Borrowed(steno),
+    };
+    let has_digits = steno.chars().any(|c| self.numbers.iter().any(|(d, _)| *d == c));
+    match has_digits && steno.contains(number_key) {
+      true => Cow::Owned(steno.replace(number_key, "")),
+      false => Cow::Borrowed(steno),
+    }
+  }
+
+  /// A key that sorts outlines in steno order, stroke by stroke. Strokes
+  /// that are not valid steno sort after all valid ones, by their
+  /// characters.
+  pub fn steno_sort_key(&self, steno: &str) -> Vec<Vec<usize>> {
+    steno.split('/')
+      .map(|stroke| match self.parse_stroke(stroke) {
+        Ok(stroke) => stroke.positions(),
+        Err(_) => iter::once(usize::MAX).chain(stroke.chars().map(|c| c as usize)).collect(),
+      })
+      .collect()
+  }
+}
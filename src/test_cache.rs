@@ -0,0 +1,91 @@
+use std::env;
+use std::fs;
+
+use crate::cache::{parse_rtf_cached, read_cache, write_cache};
+use crate::dict::Dictionary;
+use crate::error::Error;
+
+static SOURCE: &str = r"{\rtf1\ansi{\*\cxrev100}\cxdict{\*\cxsystem Test}
+{\*\cxs KAT}cat
+{\*\cxs KAT/-S}cats
+}
+";
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KORT" => "court");
+  add_entry!(dict, "KR-RT" => "{-|}court", "capitalized");
+  add_entry!(dict, "KOURT" => "court");
+  add_entry!(dict, "KORT/-G" => "courting");
+  dict
+}
+
+fn cache(dict: &Dictionary, source: &str) -> Vec<u8> {
+  let mut data = vec![];
+  write_cache(dict, source, &mut data).unwrap();
+  data
+}
+
+fn sorted(mut values: Vec<String>) -> Vec<String> {
+  values.sort();
+  values
+}
+
+#[test]
+fn test_round_trip() {
+  let dict = dictionary();
+  let read = read_cache(&cache(&dict, SOURCE), SOURCE).unwrap();
+  assert_eq!(read.cre_system, "Test");
+  assert_eq!(format!("{:?}", read), format!("{:?}", dict));
+  assert_eq!(read.longest_key, 2);
+  assert_eq!(read.rev_lookup("court"), Some(vec!["KORT".to_string(), "KOURT".to_string()]));
+  assert_eq!(sorted(read.normalized_rev_lookup("Court")), vec!["KORT", "KOURT", "KR-RT"]);
+  assert_eq!(read.entry("KR-RT").unwrap().comment(), Some("capitalized".to_string()));
+}
+
+#[test]
+fn test_stale() {
+  let data = cache(&dictionary(), SOURCE);
+  assert!(matches!(read_cache(&data, "{\\rtf1}"), Err(Error::StaleCache)));
+
+  let mut newer = data.clone();
+  newer[8] += 1;
+  assert!(matches!(read_cache(&newer, SOURCE), Err(Error::StaleCache)));
+}
+
+#[test]
+fn test_invalid() {
+  let data = cache(&dictionary(), SOURCE);
+  assert!(matches!(read_cache(b"not a cache", SOURCE), Err(Error::InvalidCache { .. })));
+  assert!(matches!(read_cache(&data[..data.len() - 1], SOURCE), Err(Error::InvalidCache { .. })));
+
+  let mut corrupted = data.clone();
+  let last = corrupted.len() - 1;
+  corrupted[last] ^= 1;
+  assert!(matches!(read_cache(&corrupted, SOURCE), Err(Error::InvalidCache { .. })));
+}
+
+#[test]
+fn test_parse_cached() {
+  let path = env::temp_dir().join(format!("rtfcre-test-{}.cache", std::process::id()));
+  let _ = fs::remove_file(&path);
+
+  let dict = parse_rtf_cached(SOURCE, &path).unwrap();
+  assert_eq!(dict.lookup("KAT/-S"), Some("cats".to_string()));
+  assert!(read_cache(&fs::read(&path).unwrap(), SOURCE).is_ok());
+
+  // A valid cache is used instead of the source
+  fs::write(&path, cache(&dictionary(), SOURCE)).unwrap();
+  let dict = parse_rtf_cached(SOURCE, &path).unwrap();
+  assert_eq!(dict.lookup("KORT"), Some("court".to_string()));
+
+  // Changing the source rebuilds the cache
+  let changed = SOURCE.replace("cats", "kats");
+  let dict = parse_rtf_cached(&changed, &path).unwrap();
+  assert_eq!(dict.lookup("KAT/-S"), Some("kats".to_string()));
+  assert_eq!(dict.lookup("KORT"), None);
+  assert!(read_cache(&fs::read(&path).unwrap(), &changed).is_ok());
+
+  assert!(parse_rtf_cached("not RTF", &path).is_none());
+  fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,54 @@
+use crate::conflicts::{boundary_conflicts, readings};
+use crate::dict::Dictionary;
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KAR" => "car");
+  add_entry!(dict, "PET" => "pet");
+  add_entry!(dict, "KAR/PET" => "carpet");
+  add_entry!(dict, "-S" => "{^s}");
+  add_entry!(dict, "PET/-S" => "pets");
+  add_entry!(dict, "KAR/PET/-S" => "carpets");
+  add_entry!(dict, "TKOG/-S" => "dogs");
+  add_entry!(dict, "KAT/HRAOG" => "catalog");
+  add_entry!(dict, "KAT" => "cat");
+  dict
+}
+
+#[test]
+fn test_readings() {
+  let dict = dictionary();
+  let outlines = |steno: &str| -> Vec<Vec<String>> {
+    readings(&dict, dict.entry(steno).unwrap()).iter()
+      .map(|reading| reading.iter().map(|entry| entry.steno.to_string()).collect())
+      .collect()
+  };
+  assert_eq!(outlines("KAR/PET"), vec![vec!["KAR", "PET"]]);
+  assert_eq!(outlines("KAR/PET/-S"), vec![
+    vec!["KAR", "PET", "-S"],
+    vec!["KAR", "PET/-S"],
+    vec!["KAR/PET", "-S"],
+  ]);
+  assert!(outlines("TKOG/-S").is_empty());
+  assert!(outlines("KAT/HRAOG").is_empty());
+  assert!(outlines("KAR").is_empty());
+}
+
+#[test]
+fn test_boundary_conflicts() {
+  let dict = dictionary();
+  let conflicts = boundary_conflicts(&dict);
+  let outlines: Vec<&str> = conflicts.iter().map(|conflict| &*conflict.entry.steno).collect();
+  assert_eq!(outlines, vec!["KAR/PET", "PET/-S", "KAR/PET/-S"]);
+  assert_eq!(&*conflicts[2].entry.translation, "carpets");
+  assert_eq!(conflicts[2].reading_translations(), vec![
+    "car pets",
+    "car pets",
+    "carpets",
+  ]);
+
+  let (text, reading) = &conflicts[0].readings_with_text()[0];
+  assert_eq!(text, "car pet");
+  let reading: Vec<&str> = reading.iter().map(|entry| &*entry.steno).collect();
+  assert_eq!(reading, vec!["KAR", "PET"]);
+}
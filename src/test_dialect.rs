@@ -0,0 +1,108 @@
+use crate::dialect::{
+  Dialect, ImportProfile,
+  format_plover_to_rtf_dialect, format_rtf_to_plover_with_profile};
+use crate::rtf::parse_file_with_profile;
+
+macro_rules! check_tl {
+  ($dialect:expr, $translation:literal, $formatted:literal, $warnings:literal) => {
+    let (rtf, warnings) = format_plover_to_rtf_dialect($translation, $dialect);
+    assert_eq!(rtf, String::from($formatted));
+    assert_eq!(warnings.len(), $warnings);
+  }
+}
+
+// check_tl!(<dialect>, <Plover syntax>, <RTF syntax>, <number of warnings>)
+
+#[test]
+fn test_plover() {
+  check_tl!(Dialect::Plover, "{^ing}", "{\\*\\cxplvrortho}\\cxds ing", 0);
+  check_tl!(Dialect::Plover, "{PLOVER:LOOKUP}", "{\\*\\cxplvrcmd lookup}", 0);
+  check_tl!(Dialect::Plover, "{#control(c)}", "{\\*\\cxplvrkey control(c)}", 0);
+  check_tl!(Dialect::Plover, "{#windows(r)}", "{\\*\\cxplvrkey windows(r)}", 1);
+}
+
+#[test]
+fn test_native() {
+  check_tl!(Dialect::CaseCatalyst, "test", "test", 0);
+  check_tl!(Dialect::CaseCatalyst, "{.}", "{\\cxp. }", 0);
+  check_tl!(Dialect::Eclipse, "{&a}", "{\\cxfing a}", 0);
+  check_tl!(Dialect::Eclipse, "{-|}", "\\cxfc ", 0);
+  check_tl!(Dialect::StenoCat, "{^ing}", "\\cxds ing", 0);
+  check_tl!(Dialect::StenoCat, "{un-^}", "un\\_\\cxds ", 0);
+}
+
+#[test]
+fn test_approximated() {
+  check_tl!(Dialect::CaseCatalyst, "{:stitch:a}", "{\\cxfing a}", 1);
+  check_tl!(Dialect::ProCat, "{:stitch:a}", "{\\cxfing a}", 1);
+  check_tl!(Dialect::Eclipse, "{~|^-esque}", "\\cxds \\_esque", 1);
+  check_tl!(Dialect::Eclipse, "{<}", "\\cxfc ", 1);
+}
+
+#[test]
+fn test_dropped() {
+  check_tl!(Dialect::CaseCatalyst, "{PLOVER:LOOKUP}", "", 1);
+  check_tl!(Dialect::ProCat, "{#Left}", "", 1);
+  check_tl!(Dialect::StenoCat, "{MODE:CAPS}test", "test", 1);
+
+  let (_, warnings) = format_plover_to_rtf_dialect("{*}", Dialect::Eclipse);
+  assert!(warnings[0].dropped);
+}
+
+macro_rules! check_import {
+  ($profile:expr, $rtf:literal, $translation:literal, $unmapped:expr) => {
+    let (translation, unmapped) = format_rtf_to_plover_with_profile($rtf, &$profile);
+    assert_eq!(translation, String::from($translation));
+    assert_eq!(unmapped, $unmapped.iter().map(|c: &&str| c.to_string()).collect::<Vec<String>>());
+  }
+}
+
+// check_import!(<profile>, <RTF syntax>, <Plover syntax>, <unmapped controls>)
+
+#[test]
+fn test_import_builtin() {
+  let profile = ImportProfile::for_dialect(Dialect::Eclipse);
+  check_import!(profile, "a\\emdash b", "a\u{2014}b", [] as [&str; 0]);
+  check_import!(profile, "test{\\*\\cxsvatdictflags N}", "test", [] as [&str; 0]);
+  check_import!(profile, "\\cxfoo test", "test", ["cxfoo"]);
+
+  let profile = ImportProfile::for_dialect(Dialect::ProCat);
+  check_import!(profile, "test{\\*\\cxsvatdictflags N}", "test", ["cxsvatdictflags"]);
+}
+
+#[test]
+fn test_import_vendor() {
+  // Vendor controls that aren't built in are reported until mapped
+  let mut profile = ImportProfile::for_dialect(Dialect::StenoCat);
+  check_import!(profile, "\\vendorcap test", "test", ["vendorcap"]);
+  profile.map("vendorcap", "\\cxfc");
+  check_import!(profile, "\\vendorcap test", "{-|}test", [] as [&str; 0]);
+  check_import!(profile, "a\\emdash b", "a\u{2014}b", [] as [&str; 0]);
+}
+
+#[test]
+fn test_import_custom() {
+  let mut profile = ImportProfile::new();
+  profile.map("cxfcap", "\\cxfc");
+  profile.ignore("b");
+  check_import!(profile, "\\cxfcap test", "{-|}test", [] as [&str; 0]);
+  check_import!(profile, "\\b test\\b0", "test", [] as [&str; 0]);
+}
+
+#[test]
+fn test_import_dictionary() {
+  let rtf = r#"{\rtf1\ansi{\*\cxrev100}\cxdict{\*\cxsystem Test}
+    {\*\cxs TKA*RB}\emdash
+    {\*\cxs TEFT}\cxunknown test
+  }"#;
+  match parse_file_with_profile(rtf, &ImportProfile::for_dialect(Dialect::CaseCatalyst)) {
+    Ok((_, (dict, unmapped))) => {
+      assert_eq!(dict.lookup("TKA*RB"), Some("\u{2014}".to_string()));
+      assert_eq!(dict.lookup("TEFT"), Some("test".to_string()));
+      assert_eq!(unmapped.len(), 1);
+      assert_eq!(unmapped[0].steno, "TEFT");
+      assert_eq!(unmapped[0].control, "cxunknown");
+    },
+    Err(_) => panic!("RTF parsing failed"),
+  }
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use crate::dict::Dictionary;
+use crate::translation::normalize_translation;
+
+fn sorted(mut values: Vec<String>) -> Vec<String> {
+  values.sort();
+  values
+}
+
+#[test]
+fn test_normalize() {
+  assert_eq!(normalize_translation("Court"), "court");
+  assert_eq!(normalize_translation("{-|}court"), "court");
+  assert_eq!(normalize_translation("{^ing}"), "ing");
+  assert_eq!(normalize_translation("{^}ing"), "ing");
+  assert_eq!(normalize_translation("{~|'^}"), "'");
+  assert_eq!(normalize_translation("court  {^ ^}jury"), "court jury");
+  assert_eq!(normalize_translation("{#Return}"), "");
+}
+
+#[test]
+fn test_casereverse_lookup() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KORT" => "court");
+  add_entry!(dict, "KO*RT" => "Court");
+  add_entry!(dict, "KR-RT" => "{-|}court");
+
+  assert_eq!(sorted(dict.casereverse_lookup("COURT")), vec!["Court", "court"]);
+  assert_eq!(dict.casereverse_lookup("{-|}Court"), vec!["{-|}court"]);
+  assert_eq!(dict.casereverse_lookup("jury"), Vec::<String>::new());
+}
+
+#[test]
+fn test_normalized_rev_lookup() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KORT" => "court");
+  add_entry!(dict, "KR-RT" => "{-|}court");
+  add_entry!(dict, "-G" => "{^ing}");
+  add_entry!(dict, "-LG" => "{^}ing");
+  add_entry!(dict, "R-R" => "{^\n^}");
+
+  assert_eq!(sorted(dict.normalized_rev_lookup("Court")), vec!["KORT", "KR-RT"]);
+  assert_eq!(sorted(dict.normalized_rev_lookup("{^ing}")), vec!["-G", "-LG"]);
+  assert_eq!(dict.normalized_rev_lookup("{#Return}"), Vec::<String>::new());
+}
+
+#[test]
+fn test_normalized_index_on_edit() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KORT" => "court");
+  add_entry!(dict, "KR-RT" => "{-|}court");
+
+  add_entry!(dict, "KORT" => "cart");
+  assert_eq!(dict.normalized_rev_lookup("court"), vec!["KR-RT"]);
+  assert_eq!(dict.normalized_rev_lookup("cart"), vec!["KORT"]);
+  assert_eq!(dict.rev_lookup("court"), None);
+
+  remove_entry!(dict, "KR-RT");
+  assert_eq!(dict.normalized_rev_lookup("court"), Vec::<String>::new());
+  assert_eq!(dict.casereverse_lookup("court"), Vec::<String>::new());
+  assert!(!dict.normalized_reverse_entries.contains_key("court"));
+}
+
+#[test]
+fn test_shared_strings() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "TEFT/-G" => "testing");
+  add_entry!(dict, "TEFGT" => "testing");
+  add_entry!(dict, "TEFT" => "test");
+
+  let (key, entry) = dict.entries.iter().next().unwrap();
+  assert!(Arc::ptr_eq(key, &entry.steno));
+  assert!(Arc::ptr_eq(&entry.translation, &dict.entries["TEFGT"].translation));
+  assert!(Arc::ptr_eq(&dict.reverse_entries["testing"][0], key));
+  assert_eq!(dict.rev_lookup("testing"), Some(vec!["TEFT/-G".to_string(), "TEFGT".to_string()]));
+
+  add_entry!(dict, "TEFT/-G" => "test");
+  assert_eq!(dict.rev_lookup("test"), Some(vec!["TEFT".to_string(), "TEFT/-G".to_string()]));
+  assert_eq!(dict.rev_lookup("testing"), Some(vec!["TEFGT".to_string()]));
+}
+
+#[test]
+fn test_normalize_outlines() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "KA-T" => "Kat");
+  add_entry!(dict, " TKOG / -S" => "dogs");
+  add_entry!(dict, "#T" => "2");
+  add_entry!(dict, "2" => "two");
+  add_entry!(dict, "KAX" => "kax");
+  assert_eq!(dict.len(), 6);
+  assert!(!dict.contains_key("TKOG/-S"));
+
+  let merged = dict.normalize_outlines();
+  assert!(dict.normalizes_outlines());
+  let merged: Vec<(&str, &str, &str)> = merged.iter()
+    .map(|merged| (merged.steno.as_str(), &*merged.replaced.steno, &*merged.kept.steno))
+    .collect();
+  assert_eq!(merged, vec![("KAT", "KAT", "KA-T"), ("2", "#T", "2")]);
+
+  let outlines: Vec<&str> = dict.entries.keys().map(|steno| &**steno).collect();
+  assert_eq!(outlines, vec!["KAT", "TKOG/-S", "2", "KAX"]);
+  assert_eq!(dict.lookup("KAT"), Some("Kat".to_string()));
+  assert_eq!(dict.rev_lookup("cat"), None);
+  assert_eq!(dict.lookup("TKOG/-S "), Some("dogs".to_string()));
+  assert_eq!(dict.lookup("#T"), Some("two".to_string()));
+  assert_eq!(dict.longest_key, 2);
+}
+
+#[test]
+fn test_normalize_on_edit() {
+  let mut dict = Dictionary::new("Test");
+  assert!(dict.normalize_outlines().is_empty());
+  add_entry!(dict, "#S-T" => "19");
+  assert_eq!(dict.entries.keys().map(|steno| steno.to_string()).collect::<Vec<_>>(), vec!["1-9"]);
+  assert!(dict.contains_key("1-9"));
+  assert!(dict.set_comment("#S-T", Some("number")));
+  assert_eq!(dict.entry("1-9").unwrap().comment(), Some("number".to_string()));
+  remove_entry!(dict, "1 - 9");
+  assert_eq!(dict.len(), 0);
+}
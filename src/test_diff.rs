@@ -0,0 +1,95 @@
+use crate::dict::Dictionary;
+use crate::diff::{Change, apply_patch, diff, parse_patch, write_patch};
+
+fn old() -> Dictionary {
+  let mut dict = Dictionary::new("Old");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "TKOG" => "dog", "TK means D");
+  add_entry!(dict, "KORT" => "court");
+  add_entry!(dict, "PWAOEUBG" => "bike");
+  dict
+}
+
+fn new() -> Dictionary {
+  // Same entries in a different order, with some changes
+  let mut dict = Dictionary::new("New");
+  add_entry!(dict, "KORT" => "{-|}court");
+  add_entry!(dict, "TKOG" => "dog");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "KOU" => "cow", "new");
+  dict
+}
+
+#[test]
+fn test_diff() {
+  assert_eq!(diff(&old(), &old()), vec![]);
+  assert_eq!(diff(&old(), &new()), vec![
+    Change::Recommented {
+      steno: "TKOG".to_string(), old: Some("TK means D".to_string()), new: None,
+    },
+    Change::Retranslated {
+      steno: "KORT".to_string(), old: "court".to_string(), new: "{-|}court".to_string(),
+    },
+    Change::Removed {
+      steno: "PWAOEUBG".to_string(), translation: "bike".to_string(), comment: None,
+    },
+    Change::Added {
+      steno: "KOU".to_string(), translation: "cow".to_string(), comment: Some("new".to_string()),
+    },
+  ]);
+}
+
+#[test]
+fn test_patch_round_trip() {
+  let changes = diff(&old(), &new());
+  assert_eq!(parse_patch(&write_patch(&changes)).unwrap(), changes);
+
+  assert!(parse_patch("{}").is_err());
+  assert!(parse_patch("[{\"op\": \"frobnicate\", \"steno\": \"KAT\"}]").is_err());
+  assert!(parse_patch("[{\"op\": \"add\", \"steno\": \"KAT\"}]").is_err());
+}
+
+#[test]
+fn test_apply() {
+  let mut dict = old();
+  let changes = diff(&old(), &new());
+  assert_eq!(apply_patch(&mut dict, &changes), vec![]);
+  assert_eq!(diff(&dict, &new()), vec![]);
+
+  // Applying the same patch again changes nothing
+  assert_eq!(apply_patch(&mut dict, &changes), vec![]);
+  assert_eq!(diff(&dict, &new()), vec![]);
+}
+
+#[test]
+fn test_apply_keeps_order() {
+  let mut dict = old();
+  dict.begin();
+  assert_eq!(apply_patch(&mut dict, &diff(&old(), &new())), vec![]);
+  dict.commit().unwrap();
+
+  // Changed entries stay where they were, added ones go at the end
+  let outlines: Vec<&str> = dict.entries.values().map(|entry| entry.steno()).collect();
+  assert_eq!(outlines, vec!["KAT", "TKOG", "KORT", "KOU"]);
+
+  // The whole patch can be undone
+  assert!(dict.undo().unwrap());
+  assert_eq!(diff(&dict, &old()), vec![]);
+}
+
+#[test]
+fn test_apply_conflicts() {
+  let mut dict = old();
+  add_entry!(dict, "KORT" => "cart");
+  add_entry!(dict, "KOU" => "cow");
+
+  let conflicts = apply_patch(&mut dict, &diff(&old(), &new()));
+  let conflicting: Vec<&str> = conflicts.iter().map(|c| c.change.steno()).collect();
+  assert_eq!(conflicting, vec!["KORT", "KOU"]);
+  assert_eq!(conflicts[0].current, Some(("cart".to_string(), None)));
+
+  // Everything else was still applied
+  assert_eq!(dict.lookup("KORT"), Some("cart".to_string()));
+  assert_eq!(dict.lookup("PWAOEUBG"), None);
+  assert_eq!(dict.entry("TKOG").unwrap().comment(), None);
+}
@@ -0,0 +1,191 @@
+use crate::dict::Dictionary;
+use crate::error::Error;
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "TKOG" => "dog", "TK means D");
+  add_entry!(dict, "KORT" => "court");
+  dict
+}
+
+fn outlines(dict: &Dictionary) -> Vec<&str> {
+  dict.entries.keys().map(|steno| &**steno).collect()
+}
+
+#[test]
+fn test_rollback() {
+  let mut dict = dictionary();
+  dict.begin();
+  add_entry!(dict, "KAT" => "Cat");
+  add_entry!(dict, "KAT/HROG" => "catalog");
+  remove_entry!(dict, "TKOG");
+  dict.set_comment("KORT", Some("court"));
+  assert_eq!(dict.longest_key, 2);
+  dict.rollback().unwrap();
+
+  assert_eq!(outlines(&dict), vec!["KAT", "TKOG", "KORT"]);
+  assert_eq!(dict.lookup("KAT"), Some("cat".to_string()));
+  assert_eq!(dict.entry("TKOG").unwrap().comment(), Some("TK means D".to_string()));
+  assert_eq!(dict.entry("KORT").unwrap().comment(), None);
+  assert_eq!(dict.rev_lookup("Cat"), None);
+  assert_eq!(dict.rev_lookup("cat"), Some(vec!["KAT".to_string()]));
+  assert_eq!(dict.rev_lookup("catalog"), None);
+  assert_eq!(dict.longest_key, 1);
+  assert!(!dict.can_undo());
+}
+
+#[test]
+fn test_transaction() {
+  let mut dict = dictionary();
+  let result: Result<(), &str> = dict.transaction(|dict| {
+    add_entry!(dict, "KAT" => "Cat");
+    Err("invalid")
+  });
+  assert_eq!(result, Err("invalid"));
+  assert_eq!(dict.lookup("KAT"), Some("cat".to_string()));
+
+  let result: Result<(), &str> = dict.transaction(|dict| {
+    add_entry!(dict, "KAT" => "Cat");
+    Ok(())
+  });
+  assert_eq!(result, Ok(()));
+  assert_eq!(dict.lookup("KAT"), Some("Cat".to_string()));
+  assert!(dict.can_undo());
+
+  // The edit function cannot close its own transaction, but can nest others
+  let result: Result<(), Error> = dict.transaction(|dict| {
+    add_entry!(dict, "KOU" => "cow");
+    assert!(matches!(dict.rollback(), Err(Error::NoTransaction)));
+    dict.begin();
+    add_entry!(dict, "HORS" => "horse");
+    dict.commit()
+  });
+  assert!(result.is_ok());
+  assert_eq!(dict.lookup("KOU"), Some("cow".to_string()));
+  assert_eq!(dict.lookup("HORS"), Some("horse".to_string()));
+}
+
+#[test]
+fn test_undo_position() {
+  let mut dict = dictionary();
+  dict.begin();
+  add_entry!(dict, "KAT" => "Cat");
+  assert_eq!(outlines(&dict), vec!["TKOG", "KORT", "KAT"]);
+  remove_entry!(dict, "TKOG");
+  dict.commit().unwrap();
+
+  dict.undo().unwrap();
+  assert_eq!(outlines(&dict), vec!["KAT", "TKOG", "KORT"]);
+  dict.redo().unwrap();
+  assert_eq!(outlines(&dict), vec!["KORT", "KAT"]);
+}
+
+#[test]
+fn test_undo_position_many_edits() {
+  let mut dict = Dictionary::new("Test");
+  for i in 0..50 {
+    add_entry!(dict, &format!("S{}", i) => &i.to_string());
+  }
+  let before: Vec<String> = outlines(&dict).iter().map(|s| s.to_string()).collect();
+
+  dict.begin();
+  for i in (0..50).step_by(3) {
+    add_entry!(dict, &format!("S{}", i) => "redefined");
+  }
+  for i in (1..50).step_by(4) {
+    remove_entry!(dict, &format!("S{}", i));
+  }
+  add_entry!(dict, "S1" => "back");
+  // Positions are still right after rolling back part of the transaction
+  dict.begin();
+  remove_entry!(dict, "S2");
+  add_entry!(dict, "S4" => "moved");
+  dict.rollback().unwrap();
+  remove_entry!(dict, "S0");
+  add_entry!(dict, "S50" => "new");
+  dict.commit().unwrap();
+  let after: Vec<String> = outlines(&dict).iter().map(|s| s.to_string()).collect();
+
+  dict.undo().unwrap();
+  assert_eq!(outlines(&dict), before);
+  dict.redo().unwrap();
+  assert_eq!(outlines(&dict), after);
+}
+
+#[test]
+fn test_undo_after_unrecorded_edits() {
+  let mut dict = dictionary();
+  dict.begin();
+  add_entry!(dict, "KOU" => "cow");
+  remove_entry!(dict, "TKOG");
+  dict.commit().unwrap();
+
+  // Edits outside a transaction move things around without being recorded
+  remove_entry!(dict, "KOU");
+  remove_entry!(dict, "KAT");
+  add_entry!(dict, "TKOG" => "dog");
+  add_entry!(dict, "HORS" => "horse");
+
+  dict.undo().unwrap();
+  assert_eq!(dict.lookup("KOU"), None);
+  assert_eq!(dict.lookup("TKOG"), Some("dog".to_string()));
+  assert_eq!(outlines(&dict), vec!["KORT", "TKOG", "HORS"]);
+  dict.redo().unwrap();
+  assert_eq!(outlines(&dict), vec!["KORT", "HORS", "KOU"]);
+}
+
+#[test]
+fn test_undo_redo() {
+  let mut dict = dictionary();
+  dict.begin();
+  add_entry!(dict, "TKOG" => "{-|}dog");
+  add_entry!(dict, "KAT/HROG" => "catalog");
+  dict.commit().unwrap();
+  dict.begin();
+  remove_entry!(dict, "KAT");
+  dict.commit().unwrap();
+
+  assert_eq!(dict.undo().unwrap(), true);
+  assert_eq!(dict.lookup("KAT"), Some("cat".to_string()));
+  assert_eq!(dict.undo().unwrap(), true);
+  assert_eq!(dict.lookup("TKOG"), Some("dog".to_string()));
+  assert_eq!(dict.entry("TKOG").unwrap().comment(), Some("TK means D".to_string()));
+  assert_eq!(dict.lookup("KAT/HROG"), None);
+  assert_eq!(dict.longest_key, 1);
+  assert_eq!(dict.undo().unwrap(), false);
+
+  assert_eq!(dict.redo().unwrap(), true);
+  assert_eq!(dict.lookup("TKOG"), Some("{-|}dog".to_string()));
+  assert_eq!(dict.rev_lookup("dog"), None);
+  assert_eq!(dict.longest_key, 2);
+
+  // A new transaction discards what could have been redone
+  dict.begin();
+  add_entry!(dict, "KOU" => "cow");
+  dict.commit().unwrap();
+  assert!(!dict.can_redo());
+  assert_eq!(dict.redo().unwrap(), false);
+}
+
+#[test]
+fn test_nested() {
+  let mut dict = dictionary();
+  dict.begin();
+  add_entry!(dict, "KOU" => "cow");
+  dict.begin();
+  add_entry!(dict, "HORS" => "horse");
+  dict.rollback().unwrap();
+  dict.begin();
+  add_entry!(dict, "SHAOEP" => "sheep");
+  dict.commit().unwrap();
+
+  assert!(matches!(dict.undo(), Err(Error::TransactionInProgress)));
+  dict.commit().unwrap();
+  assert!(matches!(dict.commit(), Err(Error::NoTransaction)));
+
+  assert_eq!(dict.lookup("HORS"), None);
+  dict.undo().unwrap();
+  assert_eq!(dict.lookup("KOU"), None);
+  assert_eq!(dict.lookup("SHAOEP"), None);
+}
@@ -0,0 +1,67 @@
+use crate::dict::Dictionary;
+use crate::error::Error;
+use crate::key_combo::{KeyCombo, KeyPress, validate_key_combos};
+
+macro_rules! check_combo {
+  ($combo:literal, $normalized:literal) => {
+    match KeyCombo::parse($combo) {
+      Ok(combo) => assert_eq!(combo.to_string(), $normalized),
+      Err(e) => panic!("{}", e),
+    }
+  }
+}
+
+macro_rules! check_invalid {
+  ($combo:literal) => {
+    assert!(matches!(KeyCombo::parse($combo), Err(Error::InvalidKeyCombo { .. })));
+  }
+}
+
+#[test]
+fn test_parse() {
+  assert_eq!(KeyCombo::parse("control(c)").unwrap(), KeyCombo(vec![
+    KeyPress::Hold("control".to_string(), vec![KeyPress::Key("c".to_string())]),
+  ]));
+  assert_eq!(KeyCombo::parse("Return Tab").unwrap(), KeyCombo(vec![
+    KeyPress::Key("return".to_string()),
+    KeyPress::Key("tab".to_string()),
+  ]));
+}
+
+#[test]
+fn test_normalize() {
+  check_combo!("Left", "left");
+  check_combo!("  control(c)  ", "control(c)");
+  check_combo!("SHIFT ( a   b )", "shift(a b)");
+  check_combo!("super_l(alt_l(shift(F5)))", "super_l(alt_l(shift(f5)))");
+  check_combo!("AudioRaiseVolume KP_Enter Odiaeresis", "audioraisevolume kp_enter odiaeresis");
+}
+
+#[test]
+fn test_invalid() {
+  check_invalid!("control(c");
+  check_invalid!("control)c(");
+  check_invalid!("contrl(c)");
+  check_invalid!("shift(a bee)");
+  check_invalid!("windows(r)");
+  check_invalid!("   ");
+}
+
+#[test]
+fn test_validate_translation() {
+  assert!(validate_key_combos("{#control(c)}{^}").is_ok());
+  assert!(validate_key_combos("no combos here").is_ok());
+  assert!(validate_key_combos("{#control(c}").is_err());
+}
+
+#[test]
+fn test_invalid_key_combos() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KPEU" => "{#control(c)}");
+  add_entry!(dict, "KPEUP" => "{#control(c}");
+  add_entry!(dict, "TEFT" => "test");
+  let invalid: Vec<&str> = dict.invalid_key_combos().iter()
+    .map(|(entry, _)| &*entry.steno)
+    .collect();
+  assert_eq!(invalid, vec!["KPEUP"]);
+}
@@ -0,0 +1,86 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::dict::Dictionary;
+use crate::mapped::{MappedDictionary, write_index};
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "TKOG" => "dog");
+  add_entry!(dict, "KORT" => "court", "a comment");
+  add_entry!(dict, "KOURT" => "court");
+  add_entry!(dict, "KAT/-S" => "cats");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "SKWR-RBGS" => "ǆ");
+  dict
+}
+
+fn index_path(name: &str) -> PathBuf {
+  env::temp_dir().join(format!("rtfcre-test-{}-{}.index", std::process::id(), name))
+}
+
+fn open(dict: &Dictionary, name: &str) -> MappedDictionary {
+  let path = index_path(name);
+  let mut data = vec![];
+  write_index(dict, &mut data).unwrap();
+  fs::write(&path, data).unwrap();
+  let mapped = MappedDictionary::open(&path).unwrap();
+  fs::remove_file(&path).unwrap();
+  mapped
+}
+
+#[test]
+fn test_lookup() {
+  let dict = open(&dictionary(), "lookup");
+  assert_eq!(dict.cre_system(), "Test");
+  assert_eq!(dict.len(), 6);
+  assert_eq!(dict.lookup("KAT"), Some("cat".to_string()));
+  assert_eq!(dict.lookup("KAT/-S"), Some("cats".to_string()));
+  assert_eq!(dict.lookup("SKWR-RBGS"), Some("ǆ".to_string()));
+  assert_eq!(dict.lookup("KA"), None);
+  assert!(dict.contains_key("TKOG"));
+  assert!(!dict.contains_key("TKOGS"));
+}
+
+#[test]
+fn test_rev_lookup() {
+  let dict = open(&dictionary(), "rev_lookup");
+  assert_eq!(dict.rev_lookup("court"), Some(vec!["KORT".to_string(), "KOURT".to_string()]));
+  assert_eq!(dict.rev_lookup("cats"), Some(vec!["KAT/-S".to_string()]));
+  assert_eq!(dict.rev_lookup("cow"), None);
+}
+
+#[test]
+fn test_empty() {
+  let dict = open(&Dictionary::new("Empty"), "empty");
+  assert!(dict.is_empty());
+  assert_eq!(dict.cre_system(), "Empty");
+  assert_eq!(dict.lookup("KAT"), None);
+  assert_eq!(dict.rev_lookup("cat"), None);
+}
+
+#[test]
+fn test_invalid() {
+  let path = index_path("invalid");
+  let mut data = vec![];
+  write_index(&dictionary(), &mut data).unwrap();
+
+  fs::write(&path, b"not an index, but long enough to have a header").unwrap();
+  assert!(MappedDictionary::open(&path).is_err());
+
+  // A truncated string is only found by a full check
+  fs::write(&path, &data[..data.len() - 1]).unwrap();
+  let dict = MappedDictionary::open(&path).unwrap();
+  assert!(dict.validate().is_err());
+  assert_eq!(dict.lookup("TKOG"), Some("dog".to_string()));
+  drop(dict);
+
+  fs::write(&path, &data[..data.len() - 20]).unwrap();
+  assert!(MappedDictionary::open(&path).unwrap().validate().is_err());
+
+  fs::write(&path, &data).unwrap();
+  assert!(MappedDictionary::open(&path).unwrap().validate().is_ok());
+
+  fs::remove_file(&path).unwrap();
+}
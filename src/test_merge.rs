@@ -0,0 +1,106 @@
+use crate::dict::{Dictionary, Entry};
+use crate::merge::{apply_resolutions, merge, write_resolutions};
+
+fn base() -> Dictionary {
+  let mut dict = Dictionary::new("Base");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "TKOG" => "dog");
+  add_entry!(dict, "KORT" => "court");
+  add_entry!(dict, "PWAOEUBG" => "bike");
+  add_entry!(dict, "KOU" => "cow");
+  dict
+}
+
+#[test]
+fn test_clean_merge() {
+  let mut ours = base();
+  add_entry!(ours, "KAT" => "{-|}cat");
+  add_entry!(ours, "HORS" => "horse");
+  remove_entry!(ours, "PWAOEUBG");
+
+  let mut theirs = base();
+  theirs.entry_mut("KAT").unwrap().add_comment("theirs");
+  add_entry!(theirs, "TKOG" => "Dog");
+  add_entry!(theirs, "HORS" => "horse");
+  add_entry!(theirs, "SHAOEP" => "sheep");
+
+  let merged = merge(&base(), &ours, &theirs);
+  assert_eq!(merged.conflicts, vec![]);
+  let entries: Vec<(String, String, Option<String>)> = merged.dict.entries.values()
+    .map(|entry| (entry.steno.to_string(), entry.translation.to_string(), entry.comment()))
+    .collect();
+  assert_eq!(entries, vec![
+    ("TKOG".to_string(), "Dog".to_string(), None),
+    ("KORT".to_string(), "court".to_string(), None),
+    ("KOU".to_string(), "cow".to_string(), None),
+    ("KAT".to_string(), "{-|}cat".to_string(), Some("theirs".to_string())),
+    ("HORS".to_string(), "horse".to_string(), None),
+    ("SHAOEP".to_string(), "sheep".to_string(), None),
+  ]);
+}
+
+#[test]
+fn test_conflicts() {
+  let mut ours = base();
+  add_entry!(ours, "KAT" => "Cat");
+  remove_entry!(ours, "KORT");
+  add_entry!(ours, "HORS" => "horse");
+
+  let mut theirs = base();
+  add_entry!(theirs, "KAT" => "CAT");
+  add_entry!(theirs, "KORT" => "{-|}court");
+  add_entry!(theirs, "HORS" => "hoarse");
+
+  let merged = merge(&base(), &ours, &theirs);
+  let conflicting: Vec<&str> = merged.conflicts.iter().map(|c| c.steno.as_str()).collect();
+  assert_eq!(conflicting, vec!["KAT", "HORS", "KORT"]);
+  assert_eq!(merged.conflicts[0].base, Some(Entry::new("KAT", "cat", None)));
+  assert_eq!(merged.conflicts[2].ours, None);
+
+  // Our side wins until resolved
+  assert_eq!(merged.dict.lookup("KAT"), Some("Cat".to_string()));
+  assert_eq!(merged.dict.lookup("KORT"), None);
+}
+
+#[test]
+fn test_conflict_markers() {
+  let mut ours = base();
+  add_entry!(ours, "KAT" => "Cat");
+  remove_entry!(ours, "KORT");
+  let mut theirs = base();
+  add_entry!(theirs, "KAT" => "CAT");
+  add_entry!(theirs, "KORT" => "{-|}court");
+
+  let merged = merge(&base(), &ours, &theirs);
+  let mut buf = Vec::new();
+  merged.write_conflict_markers(&mut buf).unwrap();
+  let rtf = String::from_utf8(buf).unwrap();
+  assert!(rtf.contains("{\\*\\cxs KAT}{\\cxconf [{\\cxc Cat}|{\\cxc CAT}]}\n"));
+  assert!(rtf.contains("{\\*\\cxs KORT}{\\cxconf [{\\cxc }|{\\cxc \\cxfc court}]}\n"));
+}
+
+#[test]
+fn test_resolutions() {
+  let mut ours = base();
+  add_entry!(ours, "KAT" => "Cat");
+  add_entry!(ours, "TKOG" => "Dog");
+  remove_entry!(ours, "KORT");
+  let mut theirs = base();
+  add_entry!(theirs, "KAT" => "CAT");
+  add_entry!(theirs, "TKOG" => "DOG");
+  add_entry!(theirs, "KORT" => "{-|}court");
+
+  let mut merged = merge(&base(), &ours, &theirs);
+  let resolutions = write_resolutions(&merged.conflicts)
+    .replacen("\"resolution\": null", "\"resolution\": \"theirs\"", 1)
+    .replacen("\"resolution\": null", "\"resolution\": {\"translation\": \"dawg\"}", 1);
+  let unresolved = apply_resolutions(&mut merged.dict, &resolutions).unwrap();
+  assert_eq!(unresolved, vec!["KORT"]);
+  assert_eq!(merged.dict.lookup("KAT"), Some("CAT".to_string()));
+  assert_eq!(merged.dict.lookup("TKOG"), Some("dawg".to_string()));
+  assert_eq!(merged.dict.lookup("KORT"), None);
+
+  assert!(apply_resolutions(&mut merged.dict, "{}").is_err());
+  assert!(apply_resolutions(&mut merged.dict,
+    "[{\"steno\": \"KAT\", \"resolution\": \"mine\"}]").is_err());
+}
@@ -0,0 +1,95 @@
+use crate::dict::Dictionary;
+use crate::order::{Order, WriteOptions, steno_sort_key};
+use crate::system::NumberKey;
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "-S" => "{^s}");
+  add_entry!(dict, "KAPT" => "captain");
+  add_entry!(dict, "KAT/-S" => "cats");
+  add_entry!(dict, "S" => "is");
+  add_entry!(dict, "KA*T" => "Cat");
+  add_entry!(dict, "1-9" => "19");
+  dict
+}
+
+fn outlines(dict: &Dictionary, order: Order) -> Vec<&str> {
+  dict.entries_in_order(order).iter().map(|entry| &*entry.steno).collect()
+}
+
+#[test]
+fn test_parse_order() {
+  assert_eq!("original".parse::<Order>().ok(), Some(Order::Original));
+  assert_eq!("steno".parse::<Order>().ok(), Some(Order::Steno));
+  assert_eq!("translation".parse::<Order>().ok(), Some(Order::Translation));
+  assert_eq!("strokes".parse::<Order>().ok(), Some(Order::StrokeCount));
+  assert!("alphabetical".parse::<Order>().is_err());
+}
+
+#[test]
+fn test_steno_sort_key() {
+  assert!(steno_sort_key("KAPT") < steno_sort_key("KAT"));
+  assert!(steno_sort_key("S") < steno_sort_key("-S"));
+  assert!(steno_sort_key("KAT") < steno_sort_key("KAT/-S"));
+  assert!(steno_sort_key("KA*T") < steno_sort_key("KAPT"));
+  assert!(steno_sort_key("A*") < steno_sort_key("*"));
+  assert_eq!(steno_sort_key("1-9"), steno_sort_key("#S-T"));
+}
+
+#[test]
+fn test_original_order() {
+  let dict = dictionary();
+  assert_eq!(outlines(&dict, Order::Original),
+    vec!["KAT", "-S", "KAPT", "KAT/-S", "S", "KA*T", "1-9"]);
+}
+
+#[test]
+fn test_steno_order() {
+  let dict = dictionary();
+  assert_eq!(outlines(&dict, Order::Steno),
+    vec!["1-9", "S", "KA*T", "KAPT", "KAT", "KAT/-S", "-S"]);
+}
+
+#[test]
+fn test_translation_order() {
+  let dict = dictionary();
+  assert_eq!(outlines(&dict, Order::Translation),
+    vec!["1-9", "KAPT", "KA*T", "KAT", "KAT/-S", "S", "-S"]);
+}
+
+#[test]
+fn test_stroke_count_order() {
+  let dict = dictionary();
+  assert_eq!(outlines(&dict, Order::StrokeCount),
+    vec!["1-9", "S", "KA*T", "KAPT", "KAT", "-S", "KAT/-S"]);
+}
+
+#[test]
+fn test_write_in_order() {
+  let dict = dictionary();
+  let mut buf = vec![];
+  let options = WriteOptions { order: Order::Steno, ..WriteOptions::default() };
+  dict.write_with_options(&mut buf, &options).unwrap();
+  let rtf = String::from_utf8(buf).unwrap();
+  let kapt = rtf.find("{\\*\\cxs KAPT}").unwrap();
+  let kat = rtf.find("{\\*\\cxs KAT}").unwrap();
+  assert!(kapt < kat);
+
+  let mut default = vec![];
+  dict.write(&mut default).unwrap();
+  let mut original = vec![];
+  dict.write_with_options(&mut original, &WriteOptions::default()).unwrap();
+  assert_eq!(original, default);
+}
+
+#[test]
+fn test_write_number_key() {
+  let dict = dictionary();
+  let mut buf = vec![];
+  let options = WriteOptions { number_key: NumberKey::Explicit, ..WriteOptions::default() };
+  dict.write_with_options(&mut buf, &options).unwrap();
+  let rtf = String::from_utf8(buf).unwrap();
+  assert!(rtf.contains("{\\*\\cxs #S-T}19"));
+  assert!(!rtf.contains("1-9"));
+}
@@ -0,0 +1,62 @@
+use crate::dict::Dictionary;
+use crate::roundtrip::{check_round_trip, check_rtf_round_trip, check_rtf_translation, check_translation};
+
+macro_rules! check_lossless {
+  ($translation:literal) => {
+    assert_eq!(check_translation("TEFT", $translation), None);
+  }
+}
+
+#[test]
+fn test_lossless() {
+  check_lossless!("testing");
+  check_lossless!("{^ing}");
+  check_lossless!("{PLOVER:LOOKUP}");
+  check_lossless!("{:stop:.}");
+  check_lossless!("{&a}");
+  check_lossless!("{-|}");
+  check_lossless!("{foo}");
+  check_lossless!("{$}");
+}
+
+#[test]
+fn test_lossy() {
+  match check_translation("PAR", "{#return}{#return}") {
+    Some(entry) => {
+      assert_eq!(entry.rtf, "\\par\\s0 ");
+      assert_eq!(entry.plover_round_trip, Some("{^\n\n^}".to_string()));
+    },
+    None => panic!("paragraph should not survive a round trip"),
+  }
+}
+
+#[test]
+fn test_check_dictionary() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "TEFT" => "test");
+  add_entry!(dict, "PAR" => "{#return}{#return}");
+  add_entry!(dict, "TEFGT" => "testing");
+
+  let lossy = check_round_trip(&dict);
+  assert_eq!(lossy.len(), 1);
+  assert_eq!(lossy[0].steno, "PAR");
+}
+
+#[test]
+fn test_command_case() {
+  check_lossless!("{PLOVER:lookup}");
+  assert_eq!(check_rtf_translation("TEFT", "{\\*\\cxplvrcmd LOOKUP}"), None);
+}
+
+#[test]
+fn test_check_rtf() {
+  let rtf = r#"{\rtf1\ansi{\*\cxrev100}\cxdict{\*\cxsystem Test}
+    {\*\cxs TEFT}test
+    {\*\cxs TK-FR}{\*\cxfoo bar}
+  }"#;
+  let lossy = check_rtf_round_trip(rtf).unwrap();
+  assert_eq!(lossy.len(), 1);
+  assert_eq!(lossy[0].steno, "TK-FR");
+  assert_eq!(lossy[0].rtf, "{\\*\\cxfoo bar}");
+  assert!(lossy[0].rtf_round_trip.is_some());
+}
@@ -1,5 +1,6 @@
-use crate::dict::Dictionary;
-use crate::rtf::parse_rtf;
+use crate::dict::{Dictionary, Entry, MergedOutline};
+use crate::rtf::{EntryReader, ParseOptions, parse_rtf, parse_rtf_with_options};
+use crate::system::NumberKey;
 
 lazy_static! {
   static ref RTF: String = r#"
@@ -109,3 +110,67 @@ fn test_parse_rtf_with_weird_spacing() {
     check_tl!(dict, "TEFT" => "test");
   })
 }
+
+#[test]
+fn test_parse_with_options() {
+  let rtf = r#"{\rtf1\ansi{\*\cxrev100}\cxdict{\*\cxsystem Test}
+    {\*\cxs TEFT}test
+    {\*\cxs TEFT/-G}testing
+    {\*\cxs TEFT/G}Testing
+  }"#;
+  let (dict, merged) = parse_rtf_with_options(rtf, &ParseOptions::default()).unwrap();
+  assert_eq!(dict.len(), 3);
+  assert!(merged.is_empty());
+
+  let options = ParseOptions { normalize_outlines: true, ..ParseOptions::default() };
+  let (dict, merged) = parse_rtf_with_options(rtf, &options).unwrap();
+  assert_eq!(dict.len(), 2);
+  assert_eq!(dict.lookup("TEFT/G"), Some("Testing".to_string()));
+  assert_eq!(merged.len(), 1);
+  assert_eq!(&*merged[0].replaced.translation, "testing");
+}
+
+#[test]
+fn test_parse_number_key() {
+  let rtf = r#"{\rtf1\ansi{\*\cxrev100}\cxdict{\*\cxsystem Test}
+    {\*\cxs #1-9}nineteen
+    {\*\cxs #S-T}Nineteen
+  }"#;
+  let dict = parse_rtf(rtf).unwrap();
+  assert_eq!(dict.lookup("#1-9"), Some("nineteen".to_string()));
+  assert_eq!(dict.lookup("#S-T"), Some("Nineteen".to_string()));
+  assert_eq!(dict.len(), 2);
+
+  let options = ParseOptions { number_key: NumberKey::Strip, ..ParseOptions::default() };
+  let (dict, merged) = parse_rtf_with_options(rtf, &options).unwrap();
+  assert_eq!(dict.lookup("1-9"), Some("nineteen".to_string()));
+  assert_eq!(dict.lookup("#S-T"), Some("Nineteen".to_string()));
+  assert!(merged.is_empty());
+
+  let options = ParseOptions { number_key: NumberKey::Digits, ..ParseOptions::default() };
+  let (dict, merged) = parse_rtf_with_options(rtf, &options).unwrap();
+  assert_eq!(dict.lookup("1-9"), Some("Nineteen".to_string()));
+  assert_eq!(dict.len(), 1);
+  assert_eq!(merged, vec![MergedOutline {
+    steno: "1-9".to_string(),
+    replaced: Entry::new("#1-9", "nineteen", None),
+    kept: Entry::new("#S-T", "Nineteen", None),
+  }]);
+}
+
+#[test]
+fn test_number_key_round_trip() {
+  // Read from JSON, written as RTF and read back, as the CLI does
+  let options = ParseOptions::default();
+  let mut reader = EntryReader::new(&options, Dictionary::new("Test"));
+  reader.add_entry("#S-9".to_string(), "nineteen".to_string(), None);
+  reader.add_entry("#1-9".to_string(), "Nineteen".to_string(), None);
+  let (dict, merged) = reader.finish();
+  assert!(merged.is_empty());
+
+  let mut rtf = vec![];
+  dict.write(&mut rtf).unwrap();
+  let read = parse_rtf(std::str::from_utf8(&rtf).unwrap()).unwrap();
+  let outlines: Vec<&str> = read.entries.values().map(|entry| entry.steno()).collect();
+  assert_eq!(outlines, vec!["#S-9", "#1-9"]);
+}
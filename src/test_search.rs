@@ -0,0 +1,61 @@
+use crate::dict::Dictionary;
+use crate::search::{Pattern, PatternKind, SearchField};
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KORT" => "court");
+  add_entry!(dict, "KAT" => "cat", "K is for cat");
+  add_entry!(dict, "KORT/-G" => "courting");
+  add_entry!(dict, "KORT/SKWRUR" => "court jury");
+  add_entry!(dict, "TKOG/-G" => "dogging", "TK means D");
+  add_entry!(dict, "TKO*G" => "Dog");
+  dict
+}
+
+macro_rules! check_search {
+  ($dict:expr, $kind:ident, $field:ident, $pattern:literal => [$($steno:literal),*]) => {
+    let pattern = Pattern::new(PatternKind::$kind, $pattern).unwrap();
+    let found: Vec<&str> = $dict.search(&pattern, SearchField::$field).iter()
+      .map(|entry| &*entry.steno)
+      .collect();
+    assert_eq!(found, vec![$($steno),*] as Vec<&str>);
+  }
+}
+
+#[test]
+fn test_substring() {
+  let dict = dictionary();
+  check_search!(dict, Substring, Translation, "court" => ["KORT", "KORT/-G", "KORT/SKWRUR"]);
+  check_search!(dict, Substring, Translation, "Court" => []);
+  check_search!(dict, Substring, Outline, "-G" => ["KORT/-G", "TKOG/-G"]);
+  check_search!(dict, Substring, Comment, "TK" => ["TKOG/-G"]);
+}
+
+#[test]
+fn test_glob() {
+  let dict = dictionary();
+  check_search!(dict, Glob, Outline, "*/-G" => ["KORT/-G", "TKOG/-G"]);
+  check_search!(dict, Glob, Outline, "K*" => ["KORT", "KAT"]);
+  check_search!(dict, Glob, Outline, "K?T" => ["KAT"]);
+  check_search!(dict, Glob, Outline, "TKO\\*G" => ["TKO*G"]);
+  check_search!(dict, Glob, Outline, "[!K]*" => ["TKO*G"]);
+  check_search!(dict, Glob, Translation, "court*" => ["KORT", "KORT/-G", "KORT/SKWRUR"]);
+  check_search!(dict, Glob, Comment, "* cat" => ["KAT"]);
+}
+
+#[test]
+fn test_regex() {
+  let dict = dictionary();
+  check_search!(dict, Regex, Translation, "^(?i)d" => ["TKOG/-G", "TKO*G"]);
+  check_search!(dict, Regex, Outline, "^[^/]+$" => ["KORT", "KAT", "TKO*G"]);
+  check_search!(dict, Regex, Comment, "means" => ["TKOG/-G"]);
+}
+
+#[test]
+fn test_invalid() {
+  assert!(Pattern::new(PatternKind::Regex, "(").is_err());
+  assert!(Pattern::new(PatternKind::Glob, "[KA").is_err());
+  assert!(Pattern::new(PatternKind::Glob, "KA\\").is_err());
+  assert!("outlines".parse::<SearchField>().is_err());
+  assert!("fuzzy".parse::<PatternKind>().is_err());
+}
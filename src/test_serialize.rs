@@ -0,0 +1,72 @@
+use crate::dict::{Dictionary, Entry};
+use crate::system::StenoSystem;
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "TKOG" => "dog", "TK means D");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "KAT/-S" => "cats");
+  add_entry!(dict, "A" => "{a^}");
+  dict
+}
+
+#[test]
+fn test_serialize() {
+  let json = serde_json::to_string(&dictionary()).unwrap();
+  assert_eq!(json, concat!(
+    r#"{"cre_system":"Test","steno_system":"english","normalize":false,"entries":["#,
+    r#"{"steno":"TKOG","translation":"dog","metadata":{"comment":"TK means D"}},"#,
+    r#"{"steno":"KAT","translation":"cat"},"#,
+    r#"{"steno":"KAT/-S","translation":"cats"},"#,
+    r#"{"steno":"A","translation":"{a^}"}]}"#));
+}
+
+#[test]
+fn test_entry() {
+  let entry = Entry::new("KAT", "cat", Some("K is for cat"));
+  let json = serde_json::to_string(&entry).unwrap();
+  assert_eq!(serde_json::from_str::<Entry>(&json).unwrap(), entry);
+
+  let entry: Entry = serde_json::from_str(r#"{"steno":"KAT","translation":"cat"}"#).unwrap();
+  assert_eq!(entry, Entry::new("KAT", "cat", None));
+}
+
+#[test]
+fn test_round_trip() {
+  let dict = dictionary();
+  let json = serde_json::to_string(&dict).unwrap();
+  let read: Dictionary = serde_json::from_str(&json).unwrap();
+  assert_eq!(read.cre_system, "Test");
+  assert_eq!(read.entries.values().collect::<Vec<&Entry>>(),
+    dict.entries.values().collect::<Vec<&Entry>>());
+  assert_eq!(read.longest_key, 2);
+  assert_eq!(read.rev_lookup("cat"), Some(vec!["KAT".to_string()]));
+  assert_eq!(read.entries["TKOG"].comment(), Some("TK means D".to_string()));
+}
+
+#[test]
+fn test_round_trip_system() {
+  let mut dict = Dictionary::new("Test");
+  dict.steno_system = StenoSystem::builtin("palantype").unwrap();
+  add_entry!(dict, "PA-T" => "pat");
+  add_entry!(dict, "SAT" => "sat");
+  dict.normalize_outlines();
+  assert!(dict.contains_key("PAT"));
+
+  let json = serde_json::to_string(&dict).unwrap();
+  let read: Dictionary = serde_json::from_str(&json).unwrap();
+  assert_eq!(read.steno_system.id(), "palantype");
+  assert!(read.normalizes_outlines());
+  assert_eq!(read.entries.values().collect::<Vec<&Entry>>(),
+    dict.entries.values().collect::<Vec<&Entry>>());
+
+  assert!(serde_json::from_str::<Dictionary>(
+    r#"{"cre_system":"Test","steno_system":"no/such/system.json"}"#).is_err());
+}
+
+#[test]
+fn test_missing_system() {
+  assert!(serde_json::from_str::<Dictionary>(r#"{"entries":[]}"#).is_err());
+  let dict: Dictionary = serde_json::from_str(r#"{"cre_system":"Test"}"#).unwrap();
+  assert!(dict.entries.is_empty());
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::thread;
+
+use crate::dict::Dictionary;
+use crate::shared::SharedDictionary;
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "TKOG" => "dog");
+  dict
+}
+
+// Every outline in the reverse index translates to its translation there, and
+// the longest key matches the outlines
+fn check_consistent(dict: &Dictionary) {
+  for (translation, outlines) in &dict.reverse_entries {
+    for steno in outlines {
+      assert_eq!(dict.entries[steno].translation, *translation);
+    }
+  }
+  for entry in dict.entries.values() {
+    assert!(dict.reverse_entries[&entry.translation].contains(&entry.steno));
+  }
+  let longest = dict.entries.keys().map(|steno| steno.split('/').count()).max().unwrap_or(0);
+  assert_eq!(dict.longest_key, longest);
+}
+
+#[test]
+fn test_snapshot() {
+  let shared = SharedDictionary::new(dictionary());
+  let before = shared.snapshot();
+  shared.write(|dict| {
+    add_entry!(dict, "KAT" => "kitten");
+    add_entry!(dict, "KAT/-S" => "cats");
+  });
+
+  assert_eq!(before.lookup("KAT"), Some("cat".to_string()));
+  assert!(!before.contains_key("KAT/-S"));
+  assert_eq!(before.longest_key, 1);
+
+  assert_eq!(shared.lookup("KAT"), Some("kitten".to_string()));
+  assert_eq!(shared.rev_lookup("cats"), Some(vec!["KAT/-S".to_string()]));
+  assert_eq!(shared.rev_lookup("cat"), None);
+  assert_eq!(shared.longest_key(), 2);
+}
+
+#[test]
+fn test_write_in_place() {
+  let shared = SharedDictionary::new(dictionary());
+  let before = Arc::as_ptr(&shared.snapshot());
+  shared.write(|dict| add_entry!(dict, "KOU" => "cow"));
+  // No snapshot was held during the edit, so nothing was copied
+  assert_eq!(Arc::as_ptr(&shared.snapshot()), before);
+
+  let snapshot = shared.snapshot();
+  shared.write(|dict| add_entry!(dict, "HORS" => "horse"));
+  assert_ne!(Arc::as_ptr(&shared.snapshot()), Arc::as_ptr(&snapshot));
+  assert!(!snapshot.contains_key("HORS"));
+}
+
+#[test]
+fn test_try_write() {
+  let shared = SharedDictionary::new(dictionary());
+  let result: Result<(), &str> = shared.try_write(|dict| {
+    add_entry!(dict, "KOU" => "cow");
+    remove_entry!(dict, "KAT");
+    Err("failed")
+  });
+  assert_eq!(result, Err("failed"));
+  assert!(!shared.contains_key("KOU"));
+  assert!(shared.contains_key("KAT"));
+  let outlines: Vec<String> = shared.snapshot().entries.keys().map(|s| s.to_string()).collect();
+  assert_eq!(outlines, vec!["KAT", "TKOG"]);
+
+  assert_eq!(shared.try_write(|dict| -> Result<usize, ()> {
+    add_entry!(dict, "KOU" => "cow");
+    Ok(dict.len())
+  }), Ok(3));
+  assert!(shared.contains_key("KOU"));
+  // Nothing is kept to undo
+  assert!(!shared.snapshot().can_undo());
+}
+
+#[test]
+fn test_replace() {
+  let shared = SharedDictionary::new(dictionary());
+  shared.replace(Dictionary::new("Other"));
+  assert_eq!(shared.snapshot().cre_system, "Other");
+  assert_eq!(shared.lookup("KAT"), None);
+}
+
+#[test]
+fn test_concurrent_readers() {
+  let shared = Arc::new(SharedDictionary::new(dictionary()));
+  let readers: Vec<_> = (0..4).map(|_| {
+    let shared = shared.clone();
+    thread::spawn(move || {
+      for _ in 0..200 {
+        let snapshot = shared.snapshot();
+        check_consistent(&snapshot);
+        assert_eq!(snapshot.lookup("TKOG"), Some("dog".to_string()));
+      }
+    })
+  }).collect();
+
+  for i in 0..200 {
+    shared.write(|dict| {
+      let outline = vec!["KAT"; i % 5 + 1].join("/");
+      dict.add_entry(outline, format!("cat {}", i), None);
+      if i % 3 == 0 {
+        remove_entry!(dict, "KAT/KAT");
+      }
+    });
+  }
+  for reader in readers {
+    reader.join().unwrap();
+  }
+  check_consistent(&shared.snapshot());
+}
@@ -0,0 +1,85 @@
+use crate::dict::Dictionary;
+use crate::stack::DictionaryStack;
+
+fn stack() -> DictionaryStack {
+  let mut base = Dictionary::new("Base");
+  add_entry!(base, "KAT" => "cat");
+  add_entry!(base, "KORT" => "court");
+  add_entry!(base, "KOURT" => "court");
+  add_entry!(base, "TKOG" => "dog");
+
+  let mut team = Dictionary::new("Team");
+  add_entry!(team, "KORT" => "{-|}court");
+  add_entry!(team, "KR-RT/R-R" => "court reporter");
+
+  let mut personal = Dictionary::new("Personal");
+  add_entry!(personal, "KAT" => "cat", "same as base");
+  add_entry!(personal, "KORT" => "court");
+
+  let mut stack = DictionaryStack::new();
+  stack.push("base.rtf", base);
+  stack.push("team.rtf", team);
+  stack.push("personal.rtf", personal);
+  stack
+}
+
+#[test]
+fn test_lookup() {
+  let stack = stack();
+  let found = stack.lookup("KORT").unwrap();
+  assert_eq!(found.dictionary, "personal.rtf");
+  assert_eq!(&*found.entry.translation, "court");
+
+  let found = stack.lookup("TKOG").unwrap();
+  assert_eq!(found.dictionary, "base.rtf");
+  assert_eq!(&*found.entry.translation, "dog");
+
+  assert_eq!(stack.lookup("PWAOEUBG"), None);
+  assert_eq!(stack.longest_key(), 2);
+}
+
+#[test]
+fn test_rev_lookup() {
+  let mut stack = stack();
+  let found: Vec<(&str, &str)> = stack.rev_lookup("court").iter()
+    .map(|found| (found.dictionary, &*found.entry.steno))
+    .collect();
+  assert_eq!(found, vec![("personal.rtf", "KORT"), ("base.rtf", "KOURT")]);
+
+  // Overridden by a different translation higher up
+  stack.remove("personal.rtf");
+  assert!(stack.rev_lookup("{-|}court").iter().all(|found| found.dictionary == "team.rtf"));
+  let found: Vec<&str> = stack.rev_lookup("court").iter()
+    .map(|found| &*found.entry.steno)
+    .collect();
+  assert_eq!(found, vec!["KOURT"]);
+}
+
+#[test]
+fn test_rev_lookup_same_name() {
+  let mut lower = Dictionary::new("Lower");
+  add_entry!(lower, "KORT" => "court");
+  let mut upper = Dictionary::new("Upper");
+  add_entry!(upper, "KORT" => "{-|}court");
+
+  let mut stack = DictionaryStack::new();
+  stack.push("main.rtf", lower);
+  stack.push("main.rtf", upper);
+  assert!(stack.rev_lookup("court").is_empty());
+  assert_eq!(stack.rev_lookup("{-|}court").len(), 1);
+}
+
+#[test]
+fn test_shadowed() {
+  let stack = stack();
+  let shadowed = stack.shadowed();
+  let summary: Vec<(&str, &str, Vec<&str>)> = shadowed.iter()
+    .map(|s| (s.steno, s.active.dictionary,
+      s.shadowed.iter().map(|found| found.dictionary).collect()))
+    .collect();
+  assert_eq!(summary, vec![
+    ("KAT", "personal.rtf", vec!["base.rtf"]),
+    ("KORT", "personal.rtf", vec!["team.rtf", "base.rtf"]),
+  ]);
+  assert_eq!(&*shadowed[1].shadowed[0].entry.translation, "{-|}court");
+}
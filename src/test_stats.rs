@@ -0,0 +1,83 @@
+use crate::dict::Dictionary;
+use crate::stats::{EntryKind, stats};
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KAT" => "cat", "K is for cat");
+  add_entry!(dict, "KA*T" => "cat");
+  add_entry!(dict, "KAT/KAT" => "cat");
+  add_entry!(dict, "TKOG" => "dog");
+  add_entry!(dict, "TKOG/-S" => "dogs");
+  add_entry!(dict, "TPH-FPLT" => "{.}");
+  add_entry!(dict, "A*" => "{>}{&a}");
+  add_entry!(dict, "-G" => "{^ing}");
+  add_entry!(dict, "PHRO*FRPB" => "{PLOVER:TOGGLE}");
+  add_entry!(dict, "*" => "=undo");
+  add_entry!(dict, "KPA" => "{-|}", "capitalize next word");
+  add_entry!(dict, "TKOLG" => "dog");
+  dict
+}
+
+#[test]
+fn test_entry_kinds() {
+  let dict = dictionary();
+  let kind = |steno: &str| EntryKind::of(dict.entry(steno).unwrap());
+  assert_eq!(kind("KAT"), EntryKind::Brief);
+  assert_eq!(kind("KAT/KAT"), EntryKind::MultiStroke);
+  assert_eq!(kind("TPH-FPLT"), EntryKind::Punctuation);
+  assert_eq!(kind("A*"), EntryKind::Fingerspelling);
+  assert_eq!(kind("-G"), EntryKind::Affix);
+  assert_eq!(kind("PHRO*FRPB"), EntryKind::Command);
+  assert_eq!(kind("*"), EntryKind::Command);
+  assert_eq!(kind("KPA"), EntryKind::Other);
+}
+
+#[test]
+fn test_stats() {
+  let stats = stats(&dictionary(), 10);
+  assert_eq!(stats.entries, 12);
+  assert_eq!(stats.longest_key, 2);
+  assert_eq!(stats.stroke_counts.into_iter().collect::<Vec<_>>(), vec![(1, 10), (2, 2)]);
+  assert_eq!(stats.kinds.into_iter().collect::<Vec<_>>(), vec![
+    (EntryKind::Brief, 4),
+    (EntryKind::MultiStroke, 2),
+    (EntryKind::Fingerspelling, 1),
+    (EntryKind::Punctuation, 1),
+    (EntryKind::Affix, 1),
+    (EntryKind::Command, 2),
+    (EntryKind::Other, 1),
+  ]);
+  assert_eq!(stats.commented, 2);
+  assert_eq!(stats.most_outlines, vec![("cat".to_string(), 3), ("dog".to_string(), 2)]);
+}
+
+#[test]
+fn test_most_outlines_limit() {
+  let stats = stats(&dictionary(), 1);
+  assert_eq!(stats.most_outlines, vec![("cat".to_string(), 3)]);
+  assert!((stats.comment_coverage() - 2.0 / 12.0).abs() < 1e-9);
+  assert_eq!(crate::stats::stats(&Dictionary::new("Empty"), 10).comment_coverage(), 0.0);
+}
+
+#[test]
+fn test_longest_key_after_removal() {
+  let mut dict = dictionary();
+  remove_entry!(dict, "KAT/KAT");
+  assert_eq!(dict.longest_key, 2);
+  remove_entry!(dict, "TKOG/-S");
+  assert_eq!(dict.longest_key, 1);
+  assert_eq!(dict.stroke_counts().get(&2), None);
+
+  add_entry!(dict, "KAT/KAT/KAT" => "cats");
+  add_entry!(dict, "KAT/KAT/KAT" => "kittens");
+  assert_eq!(dict.longest_key, 3);
+  assert_eq!(dict.stroke_counts().get(&3), Some(&1));
+
+  dict.transaction(|dict| -> Result<(), ()> {
+    remove_entry!(dict, "KAT/KAT/KAT");
+    Ok(())
+  }).unwrap();
+  assert_eq!(dict.longest_key, 1);
+  dict.undo().unwrap();
+  assert_eq!(dict.longest_key, 3);
+}
@@ -0,0 +1,82 @@
+use crate::dict::Dictionary;
+use crate::error::Error;
+use crate::stroke::{Stroke, format_outline, parse_outline};
+
+fn keys(stroke: &str) -> Vec<&'static str> {
+  Stroke::parse(stroke).unwrap().keys()
+}
+
+fn canonical(stroke: &str) -> String {
+  Stroke::parse(stroke).unwrap().to_string()
+}
+
+fn reason(stroke: &str) -> String {
+  match Stroke::parse(stroke) {
+    Err(Error::InvalidStroke { reason, .. }) => reason,
+    other => panic!("expected an invalid stroke, got {:?}", other),
+  }
+}
+
+#[test]
+fn test_parse() {
+  assert_eq!(keys("KAT"), vec!["K-", "A-", "-T"]);
+  assert_eq!(keys("KA*T"), vec!["K-", "A-", "*", "-T"]);
+  assert_eq!(keys("-T"), vec!["-T"]);
+  assert_eq!(keys("S-"), vec!["S-"]);
+  assert_eq!(keys("SS"), vec!["S-", "-S"]);
+  assert_eq!(keys("1-9"), vec!["#", "S-", "-T"]);
+  assert_eq!(keys("#STKP"), vec!["#", "S-", "T-", "K-", "P-"]);
+  assert_eq!(keys("STKPWHRAO*EUFRPBLGTSDZ").len(), 22);
+}
+
+#[test]
+fn test_invalid() {
+  assert_eq!(reason(""), "stroke is empty");
+  assert_eq!(reason("TAK"), "'K' is out of steno order");
+  assert_eq!(reason("91"), "'1' is out of steno order");
+  assert_eq!(reason("KAX"), "'X' is not a key");
+  assert_eq!(reason("K-A"), "'A' is out of steno order");
+  assert_eq!(reason("K--T"), "hyphen is out of place");
+  assert_eq!(reason("KAT-"), "hyphen is out of place");
+  assert!(parse_outline("KAT/TAK").is_err());
+}
+
+#[test]
+fn test_canonical() {
+  assert_eq!(canonical("KAT"), "KAT");
+  assert_eq!(canonical("KA-T"), "KAT");
+  assert_eq!(canonical("TS"), "T-S");
+  assert_eq!(canonical("S-"), "S");
+  assert_eq!(canonical("-S"), "-S");
+  assert_eq!(canonical("#S-T"), "1-9");
+  assert_eq!(canonical("1-9"), "1-9");
+  assert_eq!(canonical("#STKP"), "12K3");
+  assert_eq!(canonical("#AO"), "50");
+  assert_eq!(canonical("#-Z"), "#-Z");
+  assert_eq!(canonical("#"), "#");
+  assert_eq!(format_outline(&parse_outline("KA-T/S-").unwrap()), "KAT/S");
+}
+
+#[test]
+fn test_order_and_toggle() {
+  let stroke = |s: &str| Stroke::parse(s).unwrap();
+  assert!(stroke("KA*T") < stroke("KAPT"));
+  assert!(stroke("S") < stroke("-S"));
+  assert_eq!(stroke("-S").toggle("*").to_string(), "*S");
+  assert_eq!(stroke("*S").toggle("*").to_string(), "-S");
+  assert!(stroke("KA*T").contains("*"));
+  assert!(!stroke("KAT").contains("*"));
+}
+
+#[test]
+fn test_invalid_outlines() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "TKA/KST" => "dakst");
+  add_entry!(dict, "1-9" => "19");
+  add_entry!(dict, "KAX" => "kax");
+  let invalid: Vec<&str> = dict.invalid_outlines().iter()
+    .map(|(entry, _)| &*entry.steno)
+    .collect();
+  assert_eq!(invalid, vec!["TKA/KST", "KAX"]);
+}
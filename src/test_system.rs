@@ -0,0 +1,128 @@
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::sync::Arc;
+
+use crate::dict::Dictionary;
+use crate::error::Error;
+use crate::order::Order;
+use crate::system::{NumberKey, StenoSystem};
+
+static SYSTEM: &str = r##"{
+  "name": "Tiny",
+  "keys": ["#", "S-", "T-", "A", "-T", "-S"],
+  "implicit_hyphen_keys": ["A"],
+  "number_key": "#",
+  "numbers": {"S-": "1", "-T": "9"}
+}"##;
+
+fn canonical(system: &StenoSystem, steno: &str) -> String {
+  system.format_outline(&system.parse_outline(steno).unwrap())
+}
+
+fn reason(result: Result<StenoSystem, Error>) -> String {
+  match result {
+    Err(Error::InvalidStenoSystem { reason }) => reason,
+    other => panic!("expected an invalid system, got {:?}", other),
+  }
+}
+
+#[test]
+fn test_builtin() {
+  assert_eq!(StenoSystem::builtin("english").unwrap().name(), "English Stenotype");
+  assert_eq!(StenoSystem::builtin("palantype").unwrap().name(), "Palantype");
+  assert!(StenoSystem::builtin("michela").is_none());
+  assert_eq!(StenoSystem::english().keys().len(), 23);
+}
+
+#[test]
+fn test_palantype() {
+  let system = StenoSystem::palantype();
+  let stroke = system.parse_stroke("PAT").unwrap();
+  assert_eq!(system.stroke_keys(&stroke), vec!["P-", "A-", "-T"]);
+  assert_eq!(canonical(system, "PA-T"), "PAT");
+  assert_eq!(canonical(system, "T-T/-^"), "T-T/-^");
+  assert_eq!(canonical(system, "+-+"), "+-+");
+  assert!(system.parse_stroke("KAT").is_err());
+  assert!(system.parse_stroke("1").is_err());
+  assert!(system.steno_sort_key("SAT") < system.steno_sort_key("CAT"));
+}
+
+#[test]
+fn test_from_json() {
+  let system = StenoSystem::from_json(SYSTEM).unwrap();
+  assert_eq!(system.name(), "Tiny");
+  assert_eq!(system.stroke_keys(&system.parse_stroke("1-9").unwrap()), vec!["#", "S-", "-T"]);
+  assert_eq!(canonical(&system, "#S-T"), "1-9");
+  assert_eq!(canonical(&system, "TS"), "T-S");
+  assert_eq!(canonical(&system, "SAT/-S"), "SAT/-S");
+  assert!(system.parse_stroke("TAS9").is_err());
+}
+
+#[test]
+fn test_find() {
+  let english = StenoSystem::find("english").unwrap();
+  assert_eq!(english.id(), "english");
+  assert_eq!(StenoSystem::find(english.id()).unwrap(), english);
+
+  let path = env::temp_dir().join(format!("rtfcre-test-{}-system.json", std::process::id()));
+  fs::write(&path, SYSTEM).unwrap();
+  let system = StenoSystem::find(&path.to_string_lossy()).unwrap();
+  assert_eq!(system.name(), "Tiny");
+  assert_eq!(StenoSystem::find(system.id()).unwrap(), system);
+  fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_invalid_systems() {
+  assert_eq!(reason(StenoSystem::new("Empty", &[], &[], None, &[])),
+    "must have between 1 and 64 keys");
+  assert_eq!(reason(StenoSystem::new("Twice", &["S-", "S-"], &[], None, &[])),
+    "key \"S-\" is defined more than once");
+  assert_eq!(reason(StenoSystem::new("Long", &["ST-"], &[], None, &[])),
+    "key \"ST-\" must be a single character");
+  assert_eq!(reason(StenoSystem::new("Vowel", &["S-"], &["A"], None, &[])),
+    "unknown key \"A\"");
+  assert_eq!(reason(StenoSystem::new("Numbers", &["S-"], &[], None, &[("S-", '1')])),
+    "numbers require a number key");
+  assert!(matches!(StenoSystem::from_json("[]"), Err(Error::InvalidStenoSystem { .. })));
+  assert!(matches!(StenoSystem::from_json(&SYSTEM.replace("\"1\"", "\"one\"")),
+    Err(Error::InvalidStenoSystem { .. })));
+}
+
+#[test]
+fn test_dictionary_system() {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "TAT" => "tat");
+  add_entry!(dict, "SAT" => "sat");
+  add_entry!(dict, "KAT" => "cat");
+  assert!(dict.invalid_outlines().is_empty());
+
+  dict.steno_system = Arc::new(StenoSystem::from_json(SYSTEM).unwrap());
+  let invalid: Vec<&str> = dict.invalid_outlines().iter().map(|(entry, _)| &*entry.steno).collect();
+  assert_eq!(invalid, vec!["KAT"]);
+  let sorted: Vec<&str> = dict.entries_in_order(Order::Steno).iter()
+    .map(|entry| &*entry.steno)
+    .collect();
+  assert_eq!(sorted, vec!["SAT", "TAT", "KAT"]);
+}
+
+#[test]
+fn test_write_numbers() {
+  let english = StenoSystem::english();
+  assert_eq!(english.write_numbers("#S-9", NumberKey::Digits), "1-9");
+  assert_eq!(english.write_numbers("#1-9", NumberKey::Digits), "1-9");
+  assert_eq!(english.write_numbers("1-9", NumberKey::Explicit), "#S-T");
+  assert_eq!(english.write_numbers("#S-9", NumberKey::Keep), "#S-9");
+  assert_eq!(english.write_numbers("KAT/1-9", NumberKey::Explicit), "KAT/#S-T");
+  assert_eq!(english.write_numbers("#STKPW", NumberKey::Digits), "12K3W");
+  assert_eq!(english.write_numbers("#-Z", NumberKey::Explicit), "#-Z");
+  assert!(matches!(english.write_numbers("KAT/-S", NumberKey::Digits), Cow::Borrowed(_)));
+  assert_eq!(english.write_numbers("#1-9", NumberKey::Strip), "1-9");
+  assert_eq!(english.write_numbers("#S-T", NumberKey::Strip), "#S-T");
+  assert_eq!(english.write_numbers("KAT/#1-9", NumberKey::Strip), "KAT/1-9");
+  assert_eq!("explicit".parse::<NumberKey>().unwrap(), NumberKey::Explicit);
+  assert_eq!("strip".parse::<NumberKey>().unwrap(), NumberKey::Strip);
+  assert_eq!("letters".parse::<NumberKey>().unwrap_err().to_string(),
+    "unknown number key handling \"letters\"");
+}
@@ -1,4 +1,4 @@
-use crate::translation::format_plover_to_rtf;
+use crate::translation::{format_plover_output, format_plover_to_rtf};
 
 macro_rules! check_tl {
   ($translation: literal, $formatted: literal) => {
@@ -115,8 +115,9 @@ fn test_par() {
 #[test]
 fn test_key_combo() {
   // \cxplvrkey: send KEY combo
-  check_tl!("{#Left}", "{\\*\\cxplvrkey Left}");
+  check_tl!("{#Left}", "{\\*\\cxplvrkey left}");
   check_tl!("{# ctrl_l(tab)}", "{\\*\\cxplvrkey ctrl_l(tab)}");
+  check_tl!("{#Control_L( Shift(A)  Tab )}", "{\\*\\cxplvrkey control_l(shift(a) tab)}");
 }
 
 #[test]
@@ -217,3 +218,22 @@ fn test_newline() {
 fn test_concat() {
   check_tl!("mooo啦!{#}test{*}", "mooo\\u21862 !{\\*\\cxplvrnop}test{\\*\\cxplvrast}");
 }
+
+#[test]
+fn test_unknown() {
+  // \cxplvrunk: UNKnown Plover syntax, kept exactly as written
+  check_tl!("{foo}", "{\\*\\cxplvrunk \\{foo\\}}");
+  check_tl!("{$}", "{\\*\\cxplvrunk \\{$\\}}");
+  check_tl!("a{foo\\bar}b", "a{\\*\\cxplvrunk \\{foo\\\\bar\\}}b");
+}
+
+#[test]
+fn test_output() {
+  assert_eq!(format_plover_output(&["car", "pet", "{^s}"]), "car pets");
+  assert_eq!(format_plover_output(&["{re^}", "test", "{^ing}"]), "retesting");
+  assert_eq!(format_plover_output(&["{-|}", "hello", "{,}", "world", "{.}", "bye"]),
+    "Hello, world. Bye");
+  assert_eq!(format_plover_output(&["{&a}", "{&b}", "c", "{<}", "nasa"]), "ab c NASA");
+  assert_eq!(format_plover_output(&["new", "york", "{*-|}", "{^-^}", "city"]), "new York-city");
+  assert_eq!(format_plover_output(&["{#Return}", "{^ ^}", "x"]), " x");
+}
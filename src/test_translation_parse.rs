@@ -173,3 +173,18 @@ fn test_newline() {
 fn test_concat() {
   check_tl!("mooo\\u21862 !{\\*\\cxplvrnop}test{\\*\\cxplvrast}", "mooo啦!{#}test{*}");
 }
+
+#[test]
+fn test_unknown_control() {
+  check_tl!("\\cxfoo test", "test");
+  check_tl!("{\\*\\cxfoo bar}test", "test");
+  check_tl!("{\\*\\cxfoo {\\bar x}}test", "test");
+  check_tl!("a{\\*\\cxfoo {\\bar \\}x}{y}}b", "ab");
+}
+
+#[test]
+fn test_unknown() {
+  check_tl!("{\\*\\cxplvrunk \\{foo\\}}", "{foo}");
+  check_tl!("a{\\*\\cxplvrunk \\{foo\\\\bar\\}}b", "a{foo\\bar}b");
+  check_tl!("{\\*\\cxplvrunk \\{\\u20320 \\}}", "{你}");
+}
@@ -0,0 +1,126 @@
+use crate::dict::Dictionary;
+use crate::translator::{Event, Translator};
+
+lazy_static! {
+  static ref DICT: Dictionary = {
+    let mut d = Dictionary::new("Test");
+
+    add_entry!(d, "KAT" => "cat");
+    add_entry!(d, "KA*T" => "Kat");
+    add_entry!(d, "KAT/LOG" => "catalog");
+    add_entry!(d, "KAT/LOG/-S" => "catalogs");
+    add_entry!(d, "LOG" => "log");
+    add_entry!(d, "-S" => "{^s}");
+    add_entry!(d, "*" => "=undo");
+    add_entry!(d, "#*" => "{*}");
+    add_entry!(d, "#+" => "{*+}");
+    add_entry!(d, "#S" => "{*?}");
+    add_entry!(d, "#T" => "{*!}");
+
+    d
+  };
+}
+
+macro_rules! translate {
+  ($translator:expr, $($stroke:literal),*) => {
+    { $( $translator.translate($stroke); )* }
+  }
+}
+
+fn check_output(translator: &Translator, expected: &[Option<&str>]) {
+  let output: Vec<Option<&str>> = translator.translations().iter()
+    .map(|t| t.english.as_deref())
+    .collect();
+  assert_eq!(output, expected);
+}
+
+#[test]
+fn test_single_stroke() {
+  let mut translator = Translator::new(&DICT);
+  let events = translator.translate("KAT");
+  assert_eq!(events.len(), 1);
+  assert!(matches!(&events[0], Event::Translate(t) if t.english == Some("cat".to_string())));
+}
+
+#[test]
+fn test_longest_match() {
+  let mut translator = Translator::new(&DICT);
+  translate!(translator, "KAT", "LOG");
+  check_output(&translator, &[Some("catalog")]);
+
+  translate!(translator, "-S");
+  check_output(&translator, &[Some("catalogs")]);
+  assert_eq!(translator.translations()[0].outline(), "KAT/LOG/-S");
+}
+
+#[test]
+fn test_replacement_events() {
+  let mut translator = Translator::new(&DICT);
+  translator.translate("KAT");
+  let events = translator.translate("LOG");
+  assert_eq!(events.len(), 2);
+  assert!(matches!(&events[0], Event::Undo(t) if t.outline() == "KAT"));
+  assert!(matches!(&events[1], Event::Translate(t) if t.outline() == "KAT/LOG"));
+}
+
+#[test]
+fn test_untranslated() {
+  let mut translator = Translator::new(&DICT);
+  translate!(translator, "TKPWHR", "KAT");
+  check_output(&translator, &[None, Some("cat")]);
+}
+
+#[test]
+fn test_undo() {
+  let mut translator = Translator::new(&DICT);
+  translate!(translator, "KAT", "LOG", "*");
+  check_output(&translator, &[Some("cat")]);
+
+  translate!(translator, "*", "*");
+  check_output(&translator, &[]);
+}
+
+#[test]
+fn test_retro() {
+  let mut translator = Translator::new(&DICT);
+  translate!(translator, "KAT", "#*");
+  check_output(&translator, &[Some("Kat")]);
+
+  let mut translator = Translator::new(&DICT);
+  translate!(translator, "LOG", "#+");
+  check_output(&translator, &[Some("log"), Some("log")]);
+
+  let mut translator = Translator::new(&DICT);
+  translate!(translator, "LOG", "KAT", "#T");
+  check_output(&translator, &[Some("log{^}cat")]);
+  translate!(translator, "*");
+  check_output(&translator, &[Some("log"), Some("cat")]);
+
+  let mut translator = Translator::new(&DICT);
+  translate!(translator, "LOG", "KAT", "#S");
+  check_output(&translator, &[Some("log{^ ^}cat")]);
+}
+
+#[test]
+fn test_toggle_star() {
+  let mut translator = Translator::new(&DICT);
+  translate!(translator, "-S", "#*");
+  assert_eq!(translator.translations()[0].outline(), "*S");
+  translate!(translator, "#*");
+  assert_eq!(translator.translations()[0].outline(), "-S");
+}
+
+#[test]
+fn test_undo_levels() {
+  // Never fewer than the longest outline needs
+  let mut translator = Translator::new(&DICT);
+  translator.set_undo_levels(1);
+  translate!(translator, "LOG", "LOG", "LOG", "LOG", "LOG");
+  check_output(&translator, &[Some("log"), Some("log"), Some("log")]);
+
+  translator.set_undo_levels(4);
+  translate!(translator, "LOG", "LOG");
+  check_output(&translator, &[Some("log"), Some("log"), Some("log"), Some("log")]);
+  translate!(translator, "*", "*", "*", "*", "*");
+  check_output(&translator, &[]);
+}
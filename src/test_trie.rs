@@ -0,0 +1,76 @@
+use crate::dict::Dictionary;
+use crate::trie::StrokeTrie;
+
+fn dictionary() -> Dictionary {
+  let mut dict = Dictionary::new("Test");
+  add_entry!(dict, "KAT" => "cat");
+  add_entry!(dict, "KAT/HROG" => "catalog");
+  add_entry!(dict, "KAT/A/HROG" => "catalog");
+  add_entry!(dict, "KAT/TPH" => "catfish");
+  add_entry!(dict, "TKOG" => "dog");
+  dict
+}
+
+#[test]
+fn test_trie() {
+  let mut trie = StrokeTrie::new();
+  trie.insert("KAT/HROG");
+  assert!(!trie.contains("KAT"));
+  assert!(trie.contains("KAT/HROG"));
+  assert!(trie.has_continuation("KAT"));
+  assert!(!trie.has_continuation("KAT/HROG"));
+
+  trie.insert("KAT");
+  trie.remove("KAT/HROG");
+  assert!(trie.contains("KAT"));
+  assert!(!trie.has_continuation("KAT"));
+  assert_eq!(trie.with_prefix(""), vec!["KAT"]);
+}
+
+#[test]
+fn test_prefix() {
+  let mut dict = dictionary();
+  let expected = vec!["KAT", "KAT/A/HROG", "KAT/HROG", "KAT/TPH"];
+  assert_eq!(dict.outlines_with_prefix("KAT"), expected);
+  assert_eq!(dict.outlines_with_prefix("KAT/"), expected);
+  assert_eq!(dict.outlines_with_prefix("KA"), Vec::<String>::new());
+
+  dict.enable_trie();
+  assert_eq!(dict.outlines_with_prefix("KAT"), expected);
+  assert_eq!(dict.outlines_with_prefix("KAT/A"), vec!["KAT/A/HROG"]);
+  assert_eq!(dict.outlines_with_prefix("KA"), Vec::<String>::new());
+}
+
+#[test]
+fn test_continuation() {
+  let mut dict = dictionary();
+  for &trie in &[false, true] {
+    if trie {
+      dict.enable_trie();
+    }
+    assert!(dict.has_continuation("KAT"));
+    assert!(dict.has_continuation("KAT/A"));
+    assert!(!dict.has_continuation("KAT/HROG"));
+    assert!(!dict.has_continuation("TKOG"));
+    assert!(!dict.has_continuation("PWAOEUBG"));
+  }
+}
+
+#[test]
+fn test_sync() {
+  let mut dict = dictionary();
+  dict.enable_trie();
+
+  remove_entry!(dict, "KAT/A/HROG");
+  assert!(!dict.has_continuation("KAT/A"));
+  remove_entry!(dict, "KAT/HROG");
+  remove_entry!(dict, "KAT/TPH");
+  assert!(!dict.has_continuation("KAT"));
+  assert_eq!(dict.outlines_with_prefix("KAT"), vec!["KAT"]);
+
+  add_entry!(dict, "KAT/KAT" => "cat cat");
+  add_entry!(dict, "KAT/KAT" => "catcat");
+  assert!(dict.has_continuation("KAT"));
+  assert_eq!(dict.outlines_with_prefix("KAT"), vec!["KAT", "KAT/KAT"]);
+  assert!(dict.trie().unwrap().contains("KAT/KAT"));
+}
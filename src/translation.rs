@@ -2,9 +2,10 @@ use nom::IResult;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case, take_till, take_until, take_while};
 use nom::character::complete::space1;
-use nom::combinator::opt;
+use nom::combinator::{opt, recognize};
 use nom::sequence::tuple;
 
+use crate::key_combo::KeyCombo;
 use crate::translation_model::{Object, Case, ParagraphMode};
 
 macro_rules! opt {
@@ -270,9 +271,9 @@ fn currency(input: &str) -> IResult<&str, Object> {
 }
 
 fn anything_between_braces(input: &str) -> IResult<&str, Object> {
-  let (input, (_, string, _)) = tuple((
-    tag("{"), take_until("}"), tag("}")))(input)?;
-  Ok((input, Object::RawString(string.to_string())))
+  let (input, token) = recognize(tuple((
+    tag("{"), take_until("}"), tag("}"))))(input)?;
+  Ok((input, Object::Unknown(token.to_string())))
 }
 
 fn raw(input: &str) -> IResult<&str, Object> {
@@ -327,82 +328,193 @@ fn rest(input: &str) -> IResult<&str, Vec<Object>> {
   Ok((input, items))
 }
 
-fn parse_translation(input: &str) -> Vec<Object> {
+pub(crate) fn parse_translation(input: &str) -> Vec<Object> {
   match alt((macro_, rest))(input) { Ok((_, a)) => a, _ => vec![] }
 }
 
-pub fn format_plover_to_rtf(tl: &str) -> String {
+pub(crate) fn object_to_rtf(obj: &Object) -> String {
+  match obj {
+    Object::RawString(string) =>
+      string.chars().map(|c|
+        match c {
+          '{' | '}' | '\\' => format!("\\{}", c),
+          '-' => "\\_".to_string(),
+          '\n' => "\\n".to_string(),
+          '\t' => "\\t".to_string(),
+          c if (c as u32) > 255 => format!("\\u{} ", (c as u32)),
+          c => c.to_string(),
+        }
+      ).collect::<Vec<String>>().join(""),
+    // Keep the token exactly as written, only escaping what RTF requires
+    Object::Unknown(token) =>
+      format!("{{\\*\\cxplvrunk {}}}", token.chars().map(|c|
+        match c {
+          '{' | '}' | '\\' => format!("\\{}", c),
+          c if (c as u32) > 255 => format!("\\u{} ", (c as u32)),
+          c => c.to_string(),
+        }
+      ).collect::<Vec<String>>().join("")),
+    Object::Command(name, None) => format!("{{\\*\\cxplvrcmd {}}}", name),
+    Object::Command(name, Some(arg)) => format!("{{\\*\\cxplvrcmd {}:{}}}", name, arg),
+    Object::Meta(name, None) => format!("{{\\*\\cxplvrmeta {}}}", name),
+    Object::Meta(name, Some(arg)) => format!("{{\\*\\cxplvrmeta {}:{}}}", name, arg),
+    Object::Macro(name, None) => format!("{{\\*\\cxplvrmac {}}}", name),
+    Object::Macro(name, Some(arg)) => format!("{{\\*\\cxplvrmac {}:{}}}", name, arg),
+    Object::Punctuation(punct) => format!("{{\\cxp{} }}", punct),
+    Object::SpaceMode(Some(x)) if x.as_str() != " " => format!("{{\\*\\cxplvrspc {}}}", x),
+    Object::KeyCombo(keys) => format!("{{\\*\\cxplvrkey {}}}", match KeyCombo::parse(keys) {
+      Ok(combo) => combo.to_string(),
+      Err(_) => keys.trim().to_string(),
+    }),
+    Object::Fingerspell(string) => format!("{{\\cxfing {}}}", string),
+    Object::Stitch(string) => format!("{{\\cxstit {}}}", string),
+    Object::AttachSuffix(string) => format!("{{\\*\\cxplvrortho}}\\cxds {}", string),
+    Object::AttachPrefix(string) => format!("{{\\*\\cxplvrortho}}{}\\cxds ", string),
+    Object::AttachInfix(string) => format!("{{\\*\\cxplvrortho}}\\cxds {}\\cxds ", string),
+    Object::CarryCapRaw(string) => format!("{{\\*\\cxplvrccap}}{}", string),
+    Object::CarryCapSuffix(string) => format!("{{\\*\\cxplvrccap}}{{\\*\\cxplvrortho}}\\cxds {}", string),
+    Object::CarryCapPrefix(string) => format!("{{\\*\\cxplvrccap}}{{\\*\\cxplvrortho}}{}\\cxds ", string),
+    Object::CarryCapInfix(string) => format!("{{\\*\\cxplvrccap}}{{\\*\\cxplvrortho}}\\cxds {}\\cxds ", string),
+    Object::Currency(left, right) =>
+      format!("{{\\*\\cxplvrcurr {}c{}}}",
+        match left { Some(x) => x, None => "" },
+        match right { Some(x) => x, None => "" }),
+    _ => match obj {
+      Object::Noop => "{\\*\\cxplvrnop}",
+      Object::Cancel => "{\\*\\cxplvrcancel}",
+      Object::WordEnd => "{\\*\\cxplvrwdend}",
+      Object::DeleteStroke => "\\cxdstroke ",
+      Object::RepeatLastStroke => "{\\*\\cxplvrrpt}",
+      Object::RetroToggleStar => "{\\*\\cxplvrast}",
+      Object::RetroInsertSpace => "{\\*\\cxplvrrtisp}",
+      Object::RetroDeleteSpace => "{\\*\\cxplvrrtdsp}",
+      Object::ForceCapitalize => "\\cxfc ",
+      Object::RetroForceCapitalize => "{\\*\\cxplvrrtfc}",
+      Object::ForceCapitalizeWord => "{\\*\\cxplvrfcw}",
+      Object::RetroForceCapitalizeWord => "{\\*\\cxplvrrtfcw}",
+      Object::ForceLowercase => "\\cxfl ",
+      Object::RetroForceLowercase => "{\\*\\cxplvrrtfl}",
+      Object::CaseMode(case @ _) => match case {
+        Case::Sentence => "{\\*\\cxplvrcase0}",
+        Case::Lower => "{\\*\\cxplvrcase1}",
+        Case::Upper => "{\\*\\cxplvrcase2}",
+        Case::Title => "{\\*\\cxplvrcase3}",
+        Case::Camel => "{\\*\\cxplvrcase4\\cxplvrspc}",
+        Case::Snake => "{\\*\\cxplvrcase0\\cxplvrspc _}",
+      }
+      Object::SpaceMode(Some(x)) if x.as_str() == " " => "{{\\*\\cxplvrspc0}",
+      Object::SpaceMode(None) => "{\\*\\cxplvrspc0}",
+      Object::ResetCaseAndSpace => "{\\*\\cxplvrcase0\\cxplvrspc0}",
+      Object::AttachRaw => "\\cxds ",
+      Object::Paragraph(ParagraphMode::Default) => "\\par\\s0 ",
+      Object::Paragraph(ParagraphMode::Contin) => "\\par\\s1 ",
+      Object::Space => " ",
+      Object::HardSpace => "\\~",
+      _ => "",
+    }.to_string()
+  }
+}
+
+/// Reduce a Plover translation to its case-folded text, so that translations
+/// that differ only in formatting, such as `{^ing}` and `{^}ing`, compare
+/// equal.
+pub fn normalize_translation(tl: &str) -> String {
   parse_translation(tl).iter()
-    .map(|obj| {
-      match obj {
-        Object::RawString(string) =>
-          string.chars().map(|c|
-            match c {
-              '{' | '}' | '\\' => format!("\\{}", c),
-              '-' => "\\_".to_string(),
-              '\n' => "\\n".to_string(),
-              '\t' => "\\t".to_string(),
-              c if (c as u32) > 255 => format!("\\u{} ", (c as u32)),
-              c => c.to_string(),
-            }
-          ).collect::<Vec<String>>().join(""),
-        Object::Command(name, None) => format!("{{\\*\\cxplvrcmd {}}}", name),
-        Object::Command(name, Some(arg)) => format!("{{\\*\\cxplvrcmd {}:{}}}", name, arg),
-        Object::Meta(name, None) => format!("{{\\*\\cxplvrmeta {}}}", name),
-        Object::Meta(name, Some(arg)) => format!("{{\\*\\cxplvrmeta {}:{}}}", name, arg),
-        Object::Macro(name, None) => format!("{{\\*\\cxplvrmac {}}}", name),
-        Object::Macro(name, Some(arg)) => format!("{{\\*\\cxplvrmac {}:{}}}", name, arg),
-        Object::Punctuation(punct) => format!("{{\\cxp{} }}", punct),
-        Object::SpaceMode(Some(x)) if x.as_str() != " " => format!("{{\\*\\cxplvrspc {}}}", x),
-        Object::KeyCombo(keys) => format!("{{\\*\\cxplvrkey {}}}", keys.trim()),
-        Object::Fingerspell(string) => format!("{{\\cxfing {}}}", string),
-        Object::Stitch(string) => format!("{{\\cxstit {}}}", string),
-        Object::AttachSuffix(string) => format!("{{\\*\\cxplvrortho}}\\cxds {}", string),
-        Object::AttachPrefix(string) => format!("{{\\*\\cxplvrortho}}{}\\cxds ", string),
-        Object::AttachInfix(string) => format!("{{\\*\\cxplvrortho}}\\cxds {}\\cxds ", string),
-        Object::CarryCapRaw(string) => format!("{{\\*\\cxplvrccap}}{}", string),
-        Object::CarryCapSuffix(string) => format!("{{\\*\\cxplvrccap}}{{\\*\\cxplvrortho}}\\cxds {}", string),
-        Object::CarryCapPrefix(string) => format!("{{\\*\\cxplvrccap}}{{\\*\\cxplvrortho}}{}\\cxds ", string),
-        Object::CarryCapInfix(string) => format!("{{\\*\\cxplvrccap}}{{\\*\\cxplvrortho}}\\cxds {}\\cxds ", string),
-        Object::Currency(left, right) =>
-          format!("{{\\*\\cxplvrcurr {}c{}}}",
-            match left { Some(x) => x, None => "" },
-            match right { Some(x) => x, None => "" }),
-        _ => match obj {
-          Object::Noop => "{\\*\\cxplvrnop}",
-          Object::Cancel => "{\\*\\cxplvrcancel}",
-          Object::WordEnd => "{\\*\\cxplvrwdend}",
-          Object::DeleteStroke => "\\cxdstroke ",
-          Object::RepeatLastStroke => "{\\*\\cxplvrrpt}",
-          Object::RetroToggleStar => "{\\*\\cxplvrast}",
-          Object::RetroInsertSpace => "{\\*\\cxplvrrtisp}",
-          Object::RetroDeleteSpace => "{\\*\\cxplvrrtdsp}",
-          Object::ForceCapitalize => "\\cxfc ",
-          Object::RetroForceCapitalize => "{\\*\\cxplvrrtfc}",
-          Object::ForceCapitalizeWord => "{\\*\\cxplvrfcw}",
-          Object::RetroForceCapitalizeWord => "{\\*\\cxplvrrtfcw}",
-          Object::ForceLowercase => "\\cxfl ",
-          Object::RetroForceLowercase => "{\\*\\cxplvrrtfl}",
-          Object::CaseMode(case @ _) => match case {
-            Case::Sentence => "{\\*\\cxplvrcase0}",
-            Case::Lower => "{\\*\\cxplvrcase1}",
-            Case::Upper => "{\\*\\cxplvrcase2}",
-            Case::Title => "{\\*\\cxplvrcase3}",
-            Case::Camel => "{\\*\\cxplvrcase4\\cxplvrspc}",
-            Case::Snake => "{\\*\\cxplvrcase0\\cxplvrspc _}",
-          }
-          Object::SpaceMode(Some(x)) if x.as_str() == " " => "{{\\*\\cxplvrspc0}",
-          Object::SpaceMode(None) => "{\\*\\cxplvrspc0}",
-          Object::ResetCaseAndSpace => "{\\*\\cxplvrcase0\\cxplvrspc0}",
-          Object::AttachRaw => "\\cxds ",
-          Object::Paragraph(ParagraphMode::Default) => "\\par\\s0 ",
-          Object::Paragraph(ParagraphMode::Contin) => "\\par\\s1 ",
-          Object::Space => " ",
-          Object::HardSpace => "\\~",
-          _ => "",
-        }.to_string()
+    .filter_map(Object::text)
+    .collect::<String>()
+    .split_whitespace()
+    .collect::<Vec<&str>>()
+    .join(" ")
+    .to_lowercase()
+}
+
+// Capitalization waiting to be applied to the next word written
+#[derive(Clone, Copy)]
+enum NextCase {
+  Capitalize,
+  Lowercase,
+  Upper,
+}
+
+fn apply_case(case: Option<NextCase>, text: &str) -> String {
+  let mut chars = text.chars();
+  match (case, chars.next()) {
+    (Some(NextCase::Capitalize), Some(first)) => first.to_uppercase().chain(chars).collect(),
+    (Some(NextCase::Lowercase), Some(first)) => first.to_lowercase().chain(chars).collect(),
+    (Some(NextCase::Upper), _) => text.to_uppercase(),
+    _ => text.to_string(),
+  }
+}
+
+// Apply `case` to the last word written so far
+fn apply_case_to_last_word(output: &mut String, case: NextCase) {
+  let start = output.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+  let word = apply_case(Some(case), &output[start..]);
+  output.replace_range(start.., &word);
+}
+
+/// The text written by the Plover translations `tls` one after another:
+/// words separated by spaces unless attached, with capitalization applied.
+/// Orthography rules, commands and modes are not applied.
+pub fn format_plover_output(tls: &[&str]) -> String {
+  let mut output = String::new();
+  // Whether the next text is written without a space before it
+  let mut attach = true;
+  let mut case = None;
+  let mut glue = false;
+  for tl in tls {
+    for obj in parse_translation(tl) {
+      let (text, attach_before, attach_after) = match &obj {
+        Object::RawString(text) | Object::Stitch(text) | Object::CarryCapRaw(text) =>
+          (text.as_str(), false, false),
+        Object::Fingerspell(text) => (text.as_str(), glue, false),
+        Object::AttachPrefix(text) | Object::CarryCapPrefix(text) => (text.as_str(), false, true),
+        Object::AttachSuffix(text) | Object::CarryCapSuffix(text) => (text.as_str(), true, false),
+        Object::AttachInfix(text) | Object::CarryCapInfix(text) => (text.as_str(), true, true),
+        Object::Punctuation(text) => (text.as_str(), true, false),
+        Object::Space | Object::HardSpace => (" ", true, true),
+        Object::Paragraph(_) => ("\n\n", true, true),
+        Object::AttachRaw | Object::OrthoAttach => ("", true, true),
+        Object::ForceCapitalize => { case = Some(NextCase::Capitalize); continue },
+        Object::ForceLowercase => { case = Some(NextCase::Lowercase); continue },
+        Object::ForceCapitalizeWord => { case = Some(NextCase::Upper); continue },
+        Object::RetroForceCapitalize => {
+          apply_case_to_last_word(&mut output, NextCase::Capitalize);
+          continue
+        },
+        Object::RetroForceLowercase => {
+          apply_case_to_last_word(&mut output, NextCase::Lowercase);
+          continue
+        },
+        Object::RetroForceCapitalizeWord => {
+          apply_case_to_last_word(&mut output, NextCase::Upper);
+          continue
+        },
+        _ => continue,
+      };
+      if !(attach || attach_before) {
+        output.push(' ');
+      }
+      if !text.trim().is_empty() {
+        output.push_str(&apply_case(case.take(), text));
+      } else {
+        output.push_str(text);
+      }
+      attach = attach_after;
+      glue = matches!(obj, Object::Fingerspell(_));
+      if let Object::Punctuation(punct) = &obj {
+        if matches!(punct.as_str(), "." | "?" | "!") {
+          case = Some(NextCase::Capitalize);
+        }
       }
-    })
+    }
+  }
+  output
+}
+
+pub fn format_plover_to_rtf(tl: &str) -> String {
+  parse_translation(tl).iter()
+    .map(object_to_rtf)
     .collect::<Vec<String>>()
     .join("")
 }
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Case {
   Sentence,
   Lower,
@@ -8,13 +8,13 @@ pub enum Case {
   Snake,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ParagraphMode {
   Default,
   Contin,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Object {
   Cancel,
   Noop,
@@ -54,4 +54,22 @@ pub enum Object {
   RetroForceLowercase,
   ForceCapitalizeWord,
   RetroForceCapitalizeWord,
+  RtfControl(String, Option<String>),
+  Unknown(String),
+}
+
+impl Object {
+  /// The text this object writes out, ignoring any attachment or
+  /// capitalization it applies, or None if it writes no text of its own.
+  pub fn text(&self) -> Option<&str> {
+    match self {
+      Self::RawString(text) | Self::Fingerspell(text) | Self::Stitch(text) |
+      Self::Punctuation(text) | Self::AttachPrefix(text) |
+      Self::AttachSuffix(text) | Self::AttachInfix(text) |
+      Self::CarryCapRaw(text) | Self::CarryCapPrefix(text) |
+      Self::CarryCapSuffix(text) | Self::CarryCapInfix(text) => Some(text),
+      Self::Space | Self::HardSpace => Some(" "),
+      _ => None,
+    }
+  }
 }
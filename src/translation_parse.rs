@@ -1,9 +1,9 @@
 use nom::IResult;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_until, take_while};
-use nom::character::complete::{alpha1, digit1};
-use nom::combinator::{opt, recognize};
-use nom::multi::separated_list1;
+use nom::bytes::complete::{is_not, tag, take_until, take_while1};
+use nom::character::complete::{alpha1, anychar, digit1, none_of, one_of};
+use nom::combinator::{map, not, opt, recognize};
+use nom::multi::{many0, separated_list1};
 use nom::sequence::tuple;
 
 use std::char::from_u32;
@@ -95,6 +95,18 @@ fn arg_group(input: &str) -> IResult<&str, Object> {
   }))
 }
 
+fn unknown_token(input: &str) -> IResult<&str, Object> {
+  let (input, (_, chars, _)) = tuple((
+    tag("{\\*\\cxplvrunk "),
+    many0(alt((
+      map(tuple((tag("\\u"), positive_number, opt(tag(" ")))),
+        |(_, code, _)| from_u32(code).unwrap_or('\u{fffd}')),
+      map(tuple((tag("\\"), one_of("\\{}"))), |(_, c)| c),
+      none_of("\\}")))),
+    tag("}")))(input)?;
+  Ok((input, Object::Unknown(chars.into_iter().collect())))
+}
+
 fn auto_group(input: &str) -> IResult<&str, Object> {
   let (input, (_, _, text, _)) = tuple((
     tag("{\\cxa"), opt(tag(" ")), take_until("}"), tag("}")))(input)?;
@@ -145,8 +157,8 @@ fn dstroke(input: &str) -> IResult<&str, Object> {
 }
 
 fn fl_fc(input: &str) -> IResult<&str, Object> {
-  let (input, (_, mode, _)) = tuple((tag("\\cxf"),
-    alt((tag("l"), tag("c"))), opt(tag(" "))))(input)?;
+  let (input, (_, mode, _, _)) = tuple((tag("\\cxf"),
+    alt((tag("l"), tag("c"))), not(alpha1), opt(tag(" "))))(input)?;
   Ok((input, match mode {
     "l" => Object::ForceLowercase,
     "c" => Object::ForceCapitalize,
@@ -155,7 +167,7 @@ fn fl_fc(input: &str) -> IResult<&str, Object> {
 }
 
 fn dspace(input: &str) -> IResult<&str, Object> {
-  let (input, _) = tuple((tag("\\cxds"), opt(tag(" "))))(input)?;
+  let (input, _) = tuple((tag("\\cxds"), not(alpha1), opt(tag(" "))))(input)?;
   Ok((input, Object::AttachRaw))
 }
 
@@ -184,8 +196,37 @@ fn newline(input: &str) -> IResult<&str, Object> {
   Ok((input, Object::RawString(text.to_string())))
 }
 
+fn control_name(input: &str) -> IResult<&str, &str> {
+  recognize(tuple((alpha1, opt(number))))(input)
+}
+
+// The contents of a group up to its closing brace, keeping nested groups
+// whole so that `{\*\foo {\bar x}}` is not cut off at the first `}`.
+fn group_contents(input: &str) -> IResult<&str, &str> {
+  recognize(many0(alt((
+    is_not("\\{}"),
+    recognize(tuple((tag("\\"), anychar))),
+    recognize(tuple((tag("{"), group_contents, tag("}")))),
+  ))))(input)
+}
+
+fn unknown_group(input: &str) -> IResult<&str, Object> {
+  let (input, (_, _, name, _, arg, _)) = tuple((
+    tag("{"), opt(tag("\\*")), tuple((tag("\\"), control_name)),
+    opt(tag(" ")), group_contents, tag("}")))(input)?;
+  Ok((input, Object::RtfControl(name.1.to_string(), match arg {
+    "" => None,
+    arg => Some(arg.to_string()),
+  })))
+}
+
+fn unknown_control(input: &str) -> IResult<&str, Object> {
+  let (input, (_, name, _)) = tuple((tag("\\"), control_name, opt(tag(" "))))(input)?;
+  Ok((input, Object::RtfControl(name.to_string(), None)))
+}
+
 fn plain_text(input: &str) -> IResult<&str, Object> {
-  let (input, text) = take_while(|c| c != '\\' && c != '{')(input)?;
+  let (input, text) = take_while1(|c| c != '\\' && c != '{')(input)?;
   Ok((input, Object::RawString(text.to_string())))
 }
 
@@ -195,6 +236,7 @@ fn objects(input: &str) -> IResult<&str, Vec<Object>> {
   }
 
   let parsers = (
+    unknown_token,
     long_group,
     arg_group,
     no_arg_group,
@@ -211,6 +253,8 @@ fn objects(input: &str) -> IResult<&str, Vec<Object>> {
     hyphen,
     unicode,
     newline,
+    unknown_group,
+    unknown_control,
     plain_text,
   );
   let (input, (first, rest)) = tuple((alt(parsers), opt(objects)))(input)?;
@@ -221,7 +265,7 @@ fn objects(input: &str) -> IResult<&str, Vec<Object>> {
   Ok((input, items))
 }
 
-fn parse_translation(input: &str) -> Vec<Object> {
+pub(crate) fn parse_translation(input: &str) -> Vec<Object> {
   match objects(input) { Ok((_, a)) => a, _ => vec![] }
 }
 
@@ -255,10 +299,10 @@ fn fix_attach(translation: String) -> String {
                 translation))))))).to_string()
 }
 
-pub fn format_rtf_to_plover(tl: &str) -> String {
+pub(crate) fn objects_to_plover(objects: &[Object]) -> String {
   let mut ortho_attach = false;
 
-  let items = parse_translation(tl).iter()
+  let items = objects.iter()
     .map(|obj| {
       match obj {
         Object::Paragraph(mode) => format!("{{^\n\n^}}{}",
@@ -295,7 +339,7 @@ pub fn format_rtf_to_plover(tl: &str) -> String {
           Object::RetroInsertSpace => "{*?}",
           Object::RetroDeleteSpace => "{*!}",
           Object::HardSpace => "{^ ^}",
-          Object::RawString(string) => string,
+          Object::RawString(string) | Object::Unknown(string) => string,
           Object::ResetCaseAndSpace => "{mode:reset}",
           Object::SpaceMode(None) => "{mode:reset_space}",
           Object::AttachRaw => "{^}",
@@ -323,3 +367,7 @@ pub fn format_rtf_to_plover(tl: &str) -> String {
     items
   }
 }
+
+pub fn format_rtf_to_plover(tl: &str) -> String {
+  objects_to_plover(&parse_translation(tl))
+}
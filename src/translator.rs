@@ -0,0 +1,210 @@
+use std::rc::Rc;
+
+use crate::dict::Dictionary;
+use crate::system::StenoSystem;
+use crate::translation::parse_translation;
+use crate::translation_model::Object;
+
+/// A group of strokes translated together, along with the translations it
+/// replaced so they can be restored when it is undone. Replaced translations
+/// are shared rather than copied.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Translation {
+  pub strokes: Vec<String>,
+  /// The translation from the dictionary, or None if untranslated.
+  pub english: Option<String>,
+  pub replaced: Vec<Rc<Translation>>,
+}
+
+impl Translation {
+  pub fn outline(&self) -> String {
+    self.strokes.join("/")
+  }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Event {
+  Translate(Rc<Translation>),
+  Undo(Rc<Translation>),
+}
+
+/// How many translations are kept to undo by default, as in Plover.
+pub const DEFAULT_UNDO_LEVELS: usize = 100;
+
+enum Command {
+  Undo,
+  ToggleStar,
+  RepeatLastStroke,
+  InsertSpace,
+  DeleteSpace,
+}
+
+fn command(translation: &str) -> Option<Command> {
+  match parse_translation(translation).as_slice() {
+    [Object::DeleteStroke] => Some(Command::Undo),
+    [Object::RetroToggleStar] => Some(Command::ToggleStar),
+    [Object::RepeatLastStroke] => Some(Command::RepeatLastStroke),
+    [Object::RetroInsertSpace] => Some(Command::InsertSpace),
+    [Object::RetroDeleteSpace] => Some(Command::DeleteSpace),
+    _ => None,
+  }
+}
+
+// Add or remove the asterisk in a stroke, writing the result in canonical
+// form. Strokes that are not valid steno are left alone.
+fn toggle_star(system: &StenoSystem, stroke: &str) -> String {
+  match system.parse_stroke(stroke) {
+    Ok(stroke) => system.format_stroke(&system.toggle_key(&stroke, "*")),
+    Err(_) => stroke.to_string(),
+  }
+}
+
+/// Translates a stream of strokes with a dictionary the way Plover does,
+/// greedily matching the longest outline that ends with the latest stroke.
+/// Like Plover, it only keeps as many translations as can be undone.
+pub struct Translator<'a> {
+  dict: &'a Dictionary,
+  translations: Vec<Rc<Translation>>,
+  undo_levels: usize,
+}
+
+impl<'a> Translator<'a> {
+  pub fn new(dict: &'a Dictionary) -> Self {
+    Self { dict, translations: vec![], undo_levels: DEFAULT_UNDO_LEVELS }
+  }
+
+  /// Keep up to `undo_levels` translations to undo, or as many as the
+  /// longest outline of the dictionary needs if that is more.
+  pub fn set_undo_levels(&mut self, undo_levels: usize) {
+    self.undo_levels = undo_levels;
+    self.trim();
+  }
+
+  /// The translations currently in effect, oldest first.
+  pub fn translations(&self) -> &[Rc<Translation>] {
+    &self.translations
+  }
+
+  /// Translate a single stroke, returning the translations undone and made
+  /// as a result, in order.
+  pub fn translate(&mut self, stroke: &str) -> Vec<Event> {
+    let mut events = vec![];
+    self.translate_stroke(stroke, &mut events);
+    events
+  }
+
+  fn translate_stroke(&mut self, stroke: &str, events: &mut Vec<Event>) {
+    // Like Plover, commands are only recognized on their own stroke
+    let cmd = match self.dict.lookup(stroke) {
+      Some(translation) => command(&translation),
+      None if stroke == "*" => Some(Command::Undo),
+      None => None,
+    };
+
+    match cmd {
+      Some(Command::Undo) => self.undo(events),
+      Some(Command::ToggleStar) => {
+        if let Some(last) = self.translations.last() {
+          let toggled = toggle_star(&self.dict.steno_system, last.strokes.last().unwrap());
+          self.undo(events);
+          self.translate_stroke(&toggled, events);
+        }
+      },
+      Some(Command::RepeatLastStroke) => {
+        if let Some(last) = self.translations.last() {
+          let repeated = last.strokes.last().unwrap().clone();
+          self.translate_stroke(&repeated, events);
+        }
+      },
+      Some(Command::InsertSpace) => self.join_last_two(stroke, "{^ ^}", events),
+      Some(Command::DeleteSpace) => self.join_last_two(stroke, "{^}", events),
+      None => self.translate_outline(stroke, events),
+    }
+  }
+
+  fn translate_outline(&mut self, stroke: &str, events: &mut Vec<Event>) {
+    // Only look as far back as the longest outline in the dictionary allows
+    let mut start = self.translations.len();
+    let mut stroke_count = 1;
+    while start > 0 {
+      stroke_count += self.translations[start - 1].strokes.len();
+      if stroke_count > self.dict.longest_key {
+        break;
+      }
+      start -= 1;
+    }
+
+    let mut matched = None;
+    for i in start..self.translations.len() {
+      let mut strokes: Vec<String> = self.translations[i..].iter()
+        .flat_map(|t| t.strokes.iter().cloned())
+        .collect();
+      strokes.push(stroke.to_string());
+      if let Some(english) = self.dict.lookup(&strokes.join("/")) {
+        matched = Some((i, strokes, english));
+        break;
+      }
+    }
+
+    let (index, strokes, english) = match matched {
+      Some((i, strokes, english)) => (i, strokes, Some(english)),
+      None => (self.translations.len(), vec![stroke.to_string()], self.dict.lookup(stroke)),
+    };
+    let replaced = self.replace_from(index, events);
+    self.push(Translation { strokes, english, replaced }, events);
+  }
+
+  fn join_last_two(&mut self, stroke: &str, separator: &str, events: &mut Vec<Event>) {
+    if self.translations.len() < 2 {
+      return;
+    }
+
+    let index = self.translations.len() - 2;
+    let english = self.translations[index..].iter()
+      .map(|t| t.english.clone().unwrap_or_else(|| t.outline()))
+      .collect::<Vec<String>>()
+      .join(separator);
+    let replaced = self.replace_from(index, events);
+    self.push(Translation {
+      strokes: vec![stroke.to_string()],
+      english: Some(english),
+      replaced,
+    }, events);
+  }
+
+  fn undo(&mut self, events: &mut Vec<Event>) {
+    if let Some(last) = self.translations.pop() {
+      let restored = last.replaced.clone();
+      events.push(Event::Undo(last));
+      for translation in restored {
+        self.restore(translation, events);
+      }
+    }
+  }
+
+  fn replace_from(&mut self, index: usize, events: &mut Vec<Event>) -> Vec<Rc<Translation>> {
+    let replaced: Vec<Rc<Translation>> = self.translations.drain(index..).collect();
+    for translation in replaced.iter().rev() {
+      events.push(Event::Undo(translation.clone()));
+    }
+    replaced
+  }
+
+  fn push(&mut self, translation: Translation, events: &mut Vec<Event>) {
+    self.restore(Rc::new(translation), events);
+  }
+
+  fn restore(&mut self, translation: Rc<Translation>, events: &mut Vec<Event>) {
+    events.push(Event::Translate(translation.clone()));
+    self.translations.push(translation);
+    self.trim();
+  }
+
+  // Drop the oldest translations beyond what can be undone
+  fn trim(&mut self) {
+    let keep = self.undo_levels.max(self.dict.longest_key);
+    if self.translations.len() > keep {
+      self.translations.drain(..self.translations.len() - keep);
+    }
+  }
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+  children: BTreeMap<String, Node>,
+  terminal: bool,
+}
+
+impl Node {
+  fn outlines(&self, strokes: &mut Vec<String>, outlines: &mut Vec<String>) {
+    if self.terminal {
+      outlines.push(strokes.join("/"));
+    }
+    for (stroke, child) in &self.children {
+      strokes.push(stroke.clone());
+      child.outlines(strokes, outlines);
+      strokes.pop();
+    }
+  }
+}
+
+fn strokes(outline: &str) -> impl Iterator<Item = &str> {
+  outline.split('/').filter(|stroke| !stroke.is_empty())
+}
+
+/// An index of outlines keyed by individual strokes, for answering questions
+/// about outlines that start with a given sequence of strokes.
+#[derive(Clone, Debug, Default)]
+pub struct StrokeTrie {
+  root: Node,
+}
+
+impl StrokeTrie {
+  pub fn new() -> Self {
+    Self { root: Node::default() }
+  }
+
+  pub fn insert(&mut self, outline: &str) {
+    let mut node = &mut self.root;
+    for stroke in strokes(outline) {
+      node = node.children.entry(stroke.to_string()).or_default();
+    }
+    node.terminal = true;
+  }
+
+  pub fn remove(&mut self, outline: &str) {
+    fn remove_from(node: &mut Node, strokes: &[&str]) {
+      match strokes.split_first() {
+        None => node.terminal = false,
+        Some((first, rest)) => {
+          if let Some(child) = node.children.get_mut(*first) {
+            remove_from(child, rest);
+            // Prune branches that no longer lead to any outline
+            if !child.terminal && child.children.is_empty() {
+              node.children.remove(*first);
+            }
+          }
+        },
+      }
+    }
+
+    remove_from(&mut self.root, &strokes(outline).collect::<Vec<&str>>());
+  }
+
+  fn node(&self, prefix: &str) -> Option<&Node> {
+    let mut node = &self.root;
+    for stroke in strokes(prefix) {
+      node = node.children.get(stroke)?;
+    }
+    Some(node)
+  }
+
+  pub fn contains(&self, outline: &str) -> bool {
+    self.node(outline).map_or(false, |node| node.terminal)
+  }
+
+  /// Whether any outline is longer than `prefix` and starts with it.
+  pub fn has_continuation(&self, prefix: &str) -> bool {
+    self.node(prefix).map_or(false, |node| !node.children.is_empty())
+  }
+
+  /// All outlines starting with the strokes in `prefix`, including `prefix`
+  /// itself, ordered stroke by stroke.
+  pub fn with_prefix(&self, prefix: &str) -> Vec<String> {
+    let mut outlines = vec![];
+    if let Some(node) = self.node(prefix) {
+      let mut strokes = strokes(prefix).map(String::from).collect();
+      node.outlines(&mut strokes, &mut outlines);
+    }
+    outlines
+  }
+}